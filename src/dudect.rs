@@ -1,253 +1,5519 @@
-use core::arch::asm;
-use std::cmp::Ordering;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+use core::time::Duration;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+use std::time::Instant;
 
-use crate::statistics::TTest;
+use crate::environment::{environment_fingerprint, EnvironmentInfo};
+// unused if std is linked anyway, e.g. for the test harness
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+#[cfg(feature = "std")]
+use crate::report::ConsoleReporter;
+#[cfg(not(feature = "std"))]
+use crate::report::SilentReporter;
+use crate::report::{format_required_measurements, Reporter};
+
+#[cfg(any(
+    feature = "std",
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+))]
+pub use crate::timer::cpu_ticks;
+use crate::timer::{default_cycle_counter, CycleCounter};
+#[cfg(feature = "std")]
+use crate::timer::{serialized_cpu_ticks_begin, serialized_cpu_ticks_end};
+
+use crate::statistics::{
+    fisher_combined_p_value, kolmogorov_smirnov, overlap_coefficient, percentile_sorted,
+    MannWhitneyUTest, PairedTTest, QuantileSketch, Reservoir, RobustTTest, StatisticalTest, TTest,
+    TwoSampleHistogram, WindowedTTest, DEFAULT_T_THRESHOLD,
+};
 
 const ENOUGH_MEASUREMENTS: usize = 10000;
 const NUMBER_PERCENTILES: usize = 100;
-const TTEST_FAILED_MODERATE: f64 = 10.0; // test failed. Pankaj likes 4.5 but let's be more lenient
+/// The confidence level of the reported interval of the difference of the mean execution times.
+const CONFIDENCE_LEVEL: f64 = 0.95;
+/// The confidence level of the equivalence test, which is established with two one-sided tests
+/// at `1 - EQUIVALENCE_CONFIDENCE_LEVEL` each, i.e. with a `2 * EQUIVALENCE_CONFIDENCE_LEVEL - 1` confidence interval.
+const EQUIVALENCE_CONFIDENCE_LEVEL: f64 = 0.95;
+/// The exponent of the curve `1 - 0.5^(exponent * (i + 1) / number_of_percentiles)` of the cropping thresholds.
+const PERCENTILE_CURVE_EXPONENT: f64 = 10.0;
+const TTEST_FAILED_MODERATE: f64 = DEFAULT_T_THRESHOLD; // test failed. Pankaj likes 4.5 but let's be more lenient
 const TTEST_FAILED_OVERWHELMINGLY: f64 = 500.0;
+const MAX_ZERO_DELTA_FRACTION: f64 = 0.5;
+const SECOND_ORDER_MIN_SAMPLES: f64 = 10000.0;
+/// The bounds for the automatically chosen number of computations per run.
+const AUTO_BATCH_MIN_COMPUTATIONS: usize = 100;
+const AUTO_BATCH_MAX_COMPUTATIONS: usize = 1_000_000;
+/// The multiple of the timing resolution below which the mean execution time is considered resolution-limited.
+const RESOLUTION_LIMITED_FACTOR: f64 = 10.0;
+/// The number of histogram bins for the overlapping coefficient of the reservoir samples.
+const OVERLAP_BINS: usize = 100;
+/// The significance level below which a distribution is considered to have drifted from the reference.
+const DRIFT_ALPHA: f64 = 1e-5;
+/// The t value of the byte values of the inputs below which the inputs of both groups are considered identical.
+const IDENTICAL_INPUTS_MAX_T: f64 = 5.0;
+/// The default number of execution times at the start of each measurement run that are not used for the statistics.
+/// The first computations of a run are affected by the cold start of the batch (caches, branch predictors).
+/// This also covers `execution_times[0]`, which is measured from the tick taken before the loop.
+const DISCARDED_LEADING_SAMPLES: usize = 10;
+/// The default number of execution times at the end of each measurement run that are not used for the statistics.
+/// dudect has no valid value for the last computation, so it is still discarded to match dudect.
+const DISCARDED_TRAILING_SAMPLES: usize = 1;
+/// The number of accepted measurement runs before the batch anomaly rejection rejects a run.
+const BATCH_ANOMALY_MIN_BATCHES: f64 = 5.0;
+/// The smallest standard deviation of the batch means relative to their mean for the batch anomaly rejection,
+/// so that the nearly identical means of a quiet machine do not lead to rejecting every small change.
+const BATCH_ANOMALY_MIN_RELATIVE_DEVIATION: f64 = 0.01;
+/// The number of consecutive rejected measurement runs after which the batch anomaly rejection assumes a persistent
+/// change of the machine and starts over with the current run as the new baseline.
+const BATCH_ANOMALY_MAX_CONSECUTIVE_REJECTIONS: usize = 3;
+/// The number of empty measurements for estimating the overhead of the timer and the measurement loop.
+const CALIBRATION_SAMPLES: usize = 1000;
+/// Whether the number of computations per measurement is tuned to the timer granularity by default.
+/// The timers of WebAssembly hosts are too coarse for measuring a single computation.
+const DEFAULT_AUTO_COMPUTATIONS_PER_MEASUREMENT: bool = cfg!(target_arch = "wasm32");
+/// The automatically chosen number of computations per measurement is the smallest power of two,
+/// whose execution time is at least this multiple of the timer granularity.
+const AUTO_MEASUREMENT_MIN_GRANULARITIES: u64 = 100;
+const AUTO_MEASUREMENT_MAX_COMPUTATIONS: usize = 1 << 20;
+/// The number of tick changes of which the smallest one is the timer granularity.
+const GRANULARITY_SAMPLES: usize = 10;
+/// The number of timer reads after which the timer is considered to be stopped.
+const GRANULARITY_MAX_READS: usize = 1_000_000;
+/// Execution times above this value, which is the largest integer that `f64` represents exactly, are dropped.
+/// They are implausible (e.g. a counter that went backwards) and would lose precision in the statistics.
+const MAX_EXECUTION_TIME: u64 = 1 << f64::MANTISSA_DIGITS;
+
+/// The input of a computation, e.g. a byte array or a struct with a key, a nonce, and a message.
+pub trait MeasurementInput: Clone {
+    /// Returns the value with which the input buffers are filled before the inputs are prepared.
+    fn initial() -> Self;
+    /// Returns the bytes of the input, if it is a plain byte sequence.
+    /// They are used to check whether the inputs of both groups differ at all.
+    /// The default implementation returns `None`, which skips the check.
+    fn bytes(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
+impl<const N: usize> MeasurementInput for [u8; N] {
+    fn initial() -> Self {
+        [0u8; N]
+    }
+
+    fn bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+impl MeasurementInput for Vec<u8> {
+    fn initial() -> Self {
+        Vec::new()
+    }
+
+    fn bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
 
 /// Each function that should be tested must implement this trait.
-pub trait MeasurementSpecimen<const N: usize> {
+/// All methods are called on the specimen that is stored in the context, so a specimen can hold
+/// precomputed state (e.g. an expanded key schedule) that is not part of the measured computation.
+pub trait MeasurementSpecimen {
+    /// The input of the computation function, e.g. `[u8; N]` for a byte array of length `N`.
+    type Input: MeasurementInput;
+    /// The output of the computation function, e.g. a tag or a ciphertext, or `()`.
+    /// The output is consumed by the context, so the compiler can not remove the computation as unused.
+    type Output;
     /// Prepares the input data for the computation function.
     /// The input_data slice should be modified accordingly and the `is_group_a` slice has the same length.
     /// It is recommended to generate different input_data for group a and b.
-    fn prepare_input_data(input_data: &mut [[u8; N]], is_group_a: &[bool]);
+    fn prepare_input_data(&mut self, input_data: &mut [Self::Input], is_group_a: &[bool]);
+    /// Prepares the input data for the warm-up run, e.g. to train caches with a different input distribution.
+    /// The default implementation uses `prepare_input_data`.
+    fn prepare_warmup_data(&mut self, input_data: &mut [Self::Input], is_group_a: &[bool]) {
+        self.prepare_input_data(input_data, is_group_a)
+    }
+    /// The computation function that is analyzed for static execution time.
+    /// The input is passed by reference, so it is not copied within the measurement.
+    fn do_one_computation(&mut self, input: &Self::Input) -> Self::Output;
+    /// The computation function for the segmented measurement mode, which calls `marks.mark()` at the boundaries
+    /// between its phases; each phase gets its own test.
+    /// The default implementation calls `do_one_computation` without recording any marks.
+    fn do_one_computation_segmented(
+        &mut self,
+        input: &Self::Input,
+        _marks: &mut MarkRecorder<'_>,
+    ) -> Self::Output {
+        self.do_one_computation(input)
+    }
+    /// Executes a deterministic warm-up routine once before the warm-up run, e.g. to reach a known cache state.
+    /// The default implementation does nothing.
+    fn warmup(&mut self) {}
+    /// Called for each computation of a measurement run with its input index, input, and execution time.
+    /// It is called after the run, so it does not affect the measurements.
+    fn on_measurement(&mut self, _index: usize, _input: &Self::Input, _delta: u64) {}
+    /// Called after a run with the input and the output of each computation, if the output verification is
+    /// enabled (see `MeasurementContext::with_output_verification`).
+    fn verify_output(&mut self, _input: &Self::Input, _output: &Self::Output) {}
+}
+
+/// Records the boundaries between the phases of a segmented computation with the cycle counter of the context,
+/// see `MeasurementSpecimen::do_one_computation_segmented`.
+pub struct MarkRecorder<'a> {
+    cycle_counter: &'a mut dyn CycleCounter,
+    marks: &'a mut Vec<u64>,
+}
+
+impl MarkRecorder<'_> {
+    /// Ends the current phase of the computation and starts the next one.
+    #[inline(always)]
+    pub fn mark(&mut self) {
+        self.marks.push(self.cycle_counter.ticks());
+    }
+}
+
+/// The thresholds and the measurement volume of a test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DudectConfig {
+    /// The number of computations that are executed for each measurement run.
+    pub number_of_computations_per_run: usize,
+    /// The number of measurements that are required before a verdict is reported.
+    pub enough_measurements: usize,
+    /// The t value above which the specimen is probably not constant time.
+    pub t_threshold_moderate: f64,
+    /// The t value above which the specimen is definitely not constant time.
+    pub t_threshold_overwhelming: f64,
+    /// Stop without leakage evidence after this number of computations (including the warm-up), if set.
+    pub max_measurements: Option<usize>,
+    /// Stop without leakage evidence after this wall-clock duration, if set (requires `std`).
+    pub max_duration: Option<Duration>,
+    /// The number of percentile tests with different cropping thresholds (must be at least 1).
+    /// Each test costs a comparison per execution time when updating the statistics.
+    pub number_of_percentiles: usize,
+    /// The exponent of the curve of the cropping thresholds: the i-th of n thresholds is the percentile
+    /// `1 - 0.5^(exponent * (i + 1) / n)`. A larger exponent moves the thresholds into the tail of the distribution.
+    pub percentile_curve_exponent: f64,
+}
+
+impl Default for DudectConfig {
+    fn default() -> Self {
+        Self {
+            number_of_computations_per_run: 500,
+            enough_measurements: ENOUGH_MEASUREMENTS,
+            t_threshold_moderate: TTEST_FAILED_MODERATE,
+            t_threshold_overwhelming: TTEST_FAILED_OVERWHELMINGLY,
+            max_measurements: None,
+            max_duration: None,
+            number_of_percentiles: NUMBER_PERCENTILES,
+            percentile_curve_exponent: PERCENTILE_CURVE_EXPONENT,
+        }
+    }
+}
+
+/// A specimen that is defined by closures instead of an own implementation of [`MeasurementSpecimen`],
+/// e.g. for testing a function inline in a unit test.
+pub struct FnSpecimen<P, C, I, O> {
+    prepare: P,
+    compute: C,
+    _types: PhantomData<(I, O)>,
+}
+
+impl<P, C, I, O> FnSpecimen<P, C, I, O> {
+    /// Create a new specimen with `prepare` as `MeasurementSpecimen::prepare_input_data`
+    /// and `compute` as `MeasurementSpecimen::do_one_computation`.
+    pub fn new(prepare: P, compute: C) -> Self {
+        Self {
+            prepare,
+            compute,
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<P, C, I, O> MeasurementSpecimen for FnSpecimen<P, C, I, O>
+where
+    P: FnMut(&mut [I], &[bool]),
+    C: FnMut(&I) -> O,
+    I: MeasurementInput,
+{
+    type Input = I;
+    type Output = O;
+
+    fn prepare_input_data(&mut self, input_data: &mut [I], is_group_a: &[bool]) {
+        (self.prepare)(input_data, is_group_a)
+    }
+
+    fn do_one_computation(&mut self, input: &I) -> O {
+        (self.compute)(input)
+    }
+}
+
+/// A function that is tested with paired inputs, which share everything except the sensitive difference.
+/// The test is applied to the differences of the execution times of each pair.
+pub trait PairedMeasurementSpecimen {
+    /// The input of the computation function, e.g. `[u8; N]` for a byte array of length `N`.
+    type Input: MeasurementInput;
+    /// The output of the computation function, which is consumed by the context.
+    type Output;
+    /// Returns the input of group a and b of one pair, which are both derived from `base_rng`.
+    fn prepare_paired(&mut self, base_rng: &mut impl RngCore) -> (Self::Input, Self::Input);
     /// The computation function that is analyzed for static execution time.
-    fn do_one_computation(input: [u8; N]);
+    fn do_one_computation(&mut self, input: &Self::Input) -> Self::Output;
+}
+
+/// A context for measuring a [`PairedMeasurementSpecimen`] with a paired t-test.
+pub struct PairedMeasurementContext<T: PairedMeasurementSpecimen> {
+    specimen: T,
+    number_of_pairs_per_run: usize,
+    enough_measurements: usize,
+    t_threshold_moderate: f64,
+    t_threshold_overwhelming: f64,
+    max_measurements: Option<usize>,
+    max_duration: Option<Duration>,
+    inputs: Vec<(T::Input, T::Input)>,
+    /// whether the input of group a is executed before the input of group b, for each pair
+    a_first: Vec<bool>,
+    /// the execution times of the input of group a and b, for each pair, or `None` if one of them was dropped
+    execution_times: Vec<Option<(u64, u64)>>,
+    test: PairedTTest,
+    cycle_counter: Box<dyn CycleCounter>,
+    reporter: Box<dyn Reporter>,
+    /// the source of the inputs and the order within each pair
+    rng: StdRng,
+    /// If set, the inputs and the order of each run are derived from the seed and the number of the run.
+    seed: Option<u64>,
+    runs_executed: usize,
+    clock: MonotonicClock,
+    start_time: Option<Duration>,
+    /// the environment of the measurements, which is collected at the first measurement run
+    environment: Option<EnvironmentInfo>,
 }
 
 /// A context holds all the necessary information for creating and executing a measurement run.
-pub struct MeasurementContext<T: MeasurementSpecimen<N>, const N: usize> {
-    _specimen: T,
+pub struct MeasurementContext<T: MeasurementSpecimen> {
+    specimen: T,
+    /// The source of the timestamps of the measurements.
+    cycle_counter: Box<dyn CycleCounter>,
+    /// The receiver of the progress and the results of the measurement runs.
+    reporter: Box<dyn Reporter>,
     /// The first tick before the first computation of a measurement run was executed.
     first_tick: u64,
     ticks: Vec<u64>,
     number_of_computations_per_run: usize,
+    /// the number of computations of the same input that are executed between two ticks
+    computations_per_measurement: usize,
+    /// whether the number of computations per measurement is chosen before the warm-up run
+    auto_computations_per_measurement: bool,
+    /// the number of times each input is measured, of which only the minimum execution time is kept
+    repetitions_per_input: usize,
+    /// how an execution time is computed from a tick that is smaller than the previous one
+    tick_overflow_policy: TickOverflowPolicy,
+    /// the number of ticks that were smaller than the previous one
+    tick_overflows: usize,
+    /// whether the execution time at each position of the current run was dropped,
+    /// because the difference of its ticks was not plausible (see `TickOverflowPolicy::execution_time`)
+    dropped: Vec<bool>,
+    /// If set, the outputs of the computations of a run are kept for `MeasurementSpecimen::verify_output`.
+    outputs: Option<Vec<T::Output>>,
+    /// whether the overhead of the timer is calibrated before the warm-up run and subtracted from the execution times
+    overhead_calibration_enabled: bool,
+    timer_calibration: Option<TimerCalibration>,
+    /// the number of measurements that are required before a verdict is reported
+    enough_measurements: usize,
+    t_threshold_moderate: f64,
+    t_threshold_overwhelming: f64,
+    /// the stop conditions, after which no more measurement runs are required
+    max_measurements: Option<usize>,
+    max_duration: Option<Duration>,
     execution_times: Vec<u64>,
     first_order_uncropped_test: TTest,
-    percentile_tests: [TTest; NUMBER_PERCENTILES],
+    /// whether the first-order uncropped test is accumulated and considered for the verdict
+    first_order_uncropped_enabled: bool,
+    /// Optional first-order test that only considers the most recent samples.
+    windowed_first_order_test: Option<WindowedTTest>,
+    /// Optional per-phase analysis of segmented computations.
+    segmented: Option<SegmentedMeasurement>,
+    /// Optional rejection of measurement runs with an anomalous mean execution time.
+    batch_anomaly_rejection: Option<BatchAnomalyRejection>,
+    /// Optional parallel measurement of the wall-clock time with its own first-order test.
+    wall_clock: Option<WallClockMeasurement>,
+    /// Optional uniform sample of the analyzed execution times of all runs.
+    reservoir: Option<Reservoir>,
+    /// Optional histogram of the analyzed execution times of all runs for the distribution tests and the
+    /// mutual information.
+    histogram: Option<TwoSampleHistogram>,
+    /// If true, the Kolmogorov-Smirnov test of the histogram is reported.
+    kolmogorov_smirnov_enabled: bool,
+    /// If true, the Anderson-Darling test of the histogram is reported.
+    anderson_darling_enabled: bool,
+    /// If true, the mutual information between the group and the bins of the histogram is reported.
+    mutual_information_enabled: bool,
+    /// Optional first-order test based on the median instead of the mean, which is considered for the verdict.
+    robust_test: Option<RobustTTest>,
+    /// Optional nonparametric rank-sum test, which is considered for the verdict.
+    mann_whitney_test: Option<MannWhitneyUTest>,
+    /// Tests provided by the user, which are fed with the analyzed execution times and considered for the verdict.
+    custom_tests: Vec<Box<dyn StatisticalTest>>,
+    percentile_tests: Vec<TTest>,
     second_order_test: TTest,
-    input_data: Vec<[u8; N]>,
+    /// If false, the second-order test is neither updated nor considered for the verdict.
+    second_order_enabled: bool,
+    /// The number of samples of the first-order uncropped test that are required before the second-order test is updated.
+    second_order_min_samples: f64,
+    /// The tests of the orders 3, 4, ... on the standardized samples (empty by default).
+    higher_order_tests: Vec<TTest>,
+    /// If set, the verdict is based on the family-wise corrected p-value instead of the t value thresholds.
+    alpha: Option<f64>,
+    /// If set, the runs end as soon as the difference of the mean execution times is shown to be within this margin.
+    equivalence_margin: Option<f64>,
+    /// The correction of the smallest p-value for the number of tests.
+    multiple_testing_correction: MultipleTestingCorrection,
+    input_data: Vec<T::Input>,
+    /// the value with which new input buffers are filled
+    initial_input: T::Input,
     is_group_a: Vec<bool>,
-    percentiles: [u64; NUMBER_PERCENTILES],
+    percentiles: Vec<u64>,
+    /// the exponent of the curve of the cropping thresholds
+    percentile_curve_exponent: f64,
+    /// If set, the number of computations per run is chosen after the warm-up run to match this duration per run.
+    auto_batch_duration: Option<Duration>,
+    /// If true, the percentiles are prepared again from the next measurement run.
+    percentiles_outdated: bool,
+    /// If set, the cropping thresholds are continuously estimated from all analyzed execution times.
+    streaming_percentiles: Option<QuantileSketch>,
+    /// If set, the percentiles are prepared again after this number of analyzed measurement runs.
+    percentile_reestimation_interval: Option<usize>,
+    /// The number of analyzed measurement runs since the percentiles were prepared.
+    runs_since_percentiles: usize,
+    /// If true, the input data and group assignment are only generated once and reused for all runs.
+    static_inputs: bool,
+    /// True after the input data was prepared at least once.
+    inputs_prepared: bool,
+    /// If set, `input_data` is unused and the inputs are taken from the pool instead.
+    input_pool: Option<InputPool<T::Input>>,
+    /// If set, the inputs and groups are taken from the corpus instead of being generated.
+    corpus: Option<Corpus<T::Input>>,
+    /// If set, the computations are executed in this order of input indices.
+    /// The ticks and execution times are stored in the order of execution.
+    execution_order: Option<Vec<usize>>,
+    /// If true, the execution order is shuffled for each measurement run.
+    shuffled_execution: bool,
+    /// If true, all computations of group a are executed before the computations of group b.
+    separate_passes: bool,
+    /// the source of the group assignment and the execution order
+    rng: StdRng,
+    /// If set, `rng` is seeded with this seed and the number of the measurement run before each run.
+    seed: Option<u64>,
+    /// The maximum fraction of execution times of a run that can be zero before the timer is considered too coarse.
+    max_zero_delta_fraction: f64,
+    /// whether execution times equal to a cropping threshold are included in the percentile tests
+    inclusive_crop: bool,
+    /// If set, the group of each computation is derived from its input after the input data was prepared.
+    input_grouping: Option<fn(&T::Input) -> bool>,
+    /// The number of execution times at the start and the end of each measurement run that are not used for the statistics.
+    discarded_leading_samples: usize,
+    discarded_trailing_samples: usize,
+    /// The number of execution times of all analyzed measurement runs that were not used for the statistics.
+    discarded_samples: usize,
+    /// The number of warm-up runs, whose measurements only prepare the percentiles.
+    warmup_runs: usize,
+    /// The number of warm-up runs that were completed, i.e. that prepared the percentiles.
+    warmup_runs_completed: usize,
+    /// The number of executed measurement runs, including the warm-up.
+    runs_executed: usize,
+    /// The number of computations that were executed for all measurement runs, including the warm-up.
+    computations_executed: usize,
+    clock: MonotonicClock,
+    /// The time of `clock` at which the first measurement run was started.
+    start_time: Option<Duration>,
+    /// The wall-clock time of the campaign before it was resumed from a checkpoint.
+    elapsed_before_resume: Duration,
+    /// The environment of the measurements, which is collected at the first measurement run.
+    environment: Option<EnvironmentInfo>,
+    last_report: Option<MeasurementReport>,
+    /// directory in which the execution times of every measurement run are written, if enabled
+    #[cfg(feature = "std")]
+    batch_recorder: Option<PathBuf>,
+    /// writer to which the analyzed execution times of all runs are streamed as CSV, if enabled
+    #[cfg(feature = "std")]
+    csv_export: Option<Box<dyn Write>>,
+    /// (number of measurements, max t, max tau) after every reporting measurement run, if enabled
+    history: Option<Vec<(f64, f64, f64)>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum MeasurementRunResult {
-    LeakageFound,
-    NoLeakageEvidenceYet,
+/// A monotonic clock for the stop conditions, the throughput, and the wall-clock measurement.
+/// Its times are the durations since its creation, which are not available without `std`.
+/// On `wasm32-unknown-unknown`, where `Instant` panics, `performance.now()` is used instead.
+#[derive(Debug, Clone, Copy)]
+struct MonotonicClock {
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    origin: Instant,
+    /// the nanoseconds of `performance.now()` at the creation
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    origin: u64,
 }
 
-impl<T: MeasurementSpecimen<N>, const N: usize> MeasurementContext<T, N> {
-    /// Create a new measurement context with the provided data.
-    pub fn new(specimen: T, number_of_computations_per_run: usize) -> Self {
+impl MonotonicClock {
+    fn new() -> Self {
         Self {
-            _specimen: specimen,
-            first_tick: 0,
-            ticks: vec![0; number_of_computations_per_run],
-            number_of_computations_per_run,
-            execution_times: vec![0; number_of_computations_per_run],
-            first_order_uncropped_test: TTest::new(),
-            percentile_tests: [TTest::new(); NUMBER_PERCENTILES],
-            second_order_test: TTest::new(),
-            input_data: vec![[0u8; N]; number_of_computations_per_run],
-            is_group_a: vec![false; number_of_computations_per_run],
-            percentiles: [0u64; NUMBER_PERCENTILES],
+            #[cfg(all(
+                feature = "std",
+                not(all(target_arch = "wasm32", target_os = "unknown"))
+            ))]
+            origin: Instant::now(),
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            origin: cpu_ticks(),
         }
     }
 
-    /// Executes a measurement run and gives back a result wether or not more runs are required.
-    pub fn execute_measurement_run(&mut self) -> MeasurementRunResult {
-        // randomize is_group_a
-        for i in &mut self.is_group_a {
-            *i = rand::random::<bool>();
+    /// Returns the time since the creation of the clock, or `None` if there is no clock.
+    #[inline(always)]
+    fn now(&self) -> Option<Duration> {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        return Some(Duration::from_nanos(
+            cpu_ticks().saturating_sub(self.origin),
+        ));
+        #[cfg(all(
+            feature = "std",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        return Some(self.origin.elapsed());
+        #[cfg(not(any(feature = "std", all(target_arch = "wasm32", target_os = "unknown"))))]
+        return None;
+    }
+}
+
+/// Measures the wall-clock time of the computations in parallel to the CPU ticks.
+struct WallClockMeasurement {
+    /// The time before the first computation of a measurement run was executed.
+    first_instant: Duration,
+    instants: Vec<Duration>,
+    /// The execution times in nanoseconds.
+    execution_times: Vec<u64>,
+    first_order_uncropped_test: TTest,
+}
+
+/// Holds the phase boundaries of segmented computations and a test per phase.
+struct SegmentedMeasurement {
+    /// The tick marks recorded by each computation of a measurement run (in the order of execution).
+    marks: Vec<Vec<u64>>,
+    /// The first-order uncropped test of each phase.
+    tests: Vec<TTest>,
+}
+
+/// Discards measurement runs whose mean execution time deviates too much from the previous runs
+/// (see `MeasurementContext::with_batch_anomaly_rejection`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchAnomalyRejection {
+    /// The maximum deviation from the mean of the batch means in units of their standard deviation.
+    max_deviation: f64,
+    /// The running statistics of the means of the accepted batches (Welford method).
+    number_batches: f64,
+    mean: f64,
+    m2: f64,
+    rejected_batches: usize,
+    /// The number of rejected batches since the last accepted one.
+    consecutive_rejections: usize,
+}
+
+impl BatchAnomalyRejection {
+    fn new(max_deviation: f64) -> Self {
+        Self {
+            max_deviation,
+            number_batches: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            rejected_batches: 0,
+            consecutive_rejections: 0,
         }
+    }
 
-        T::prepare_input_data(&mut self.input_data, &self.is_group_a);
-        self.measure();
+    /// Returns true, if the batch mean is accepted, and updates the running statistics.
+    /// Batches are only rejected after enough batches were accepted, and a run of rejected batches starts over
+    /// with a new baseline.
+    fn accept(&mut self, batch_mean: f64) -> bool {
+        if self.number_batches >= BATCH_ANOMALY_MIN_BATCHES {
+            let standard_deviation = f64::max(
+                f64::sqrt(self.m2 / (self.number_batches - 1.0)),
+                BATCH_ANOMALY_MIN_RELATIVE_DEVIATION * f64::abs(self.mean),
+            );
+            if f64::abs(batch_mean - self.mean) > self.max_deviation * standard_deviation {
+                self.consecutive_rejections += 1;
+                if self.consecutive_rejections < BATCH_ANOMALY_MAX_CONSECUTIVE_REJECTIONS {
+                    self.rejected_batches += 1;
+                    return false;
+                }
+                *self = Self {
+                    rejected_batches: self.rejected_batches,
+                    ..Self::new(self.max_deviation)
+                };
+            }
+        }
 
-        let first_time = self.percentiles[self.percentiles.len() - 1] == 0;
-        if first_time {
-            // throw away the first batch of measurements.
-            // this helps warming things up.
-            self.prepare_percentiles();
-            MeasurementRunResult::NoLeakageEvidenceYet
-        } else {
-            self.update_statistics();
-            self.report()
+        self.consecutive_rejections = 0;
+        self.number_batches += 1.0;
+        let delta = batch_mean - self.mean;
+        self.mean += delta / self.number_batches;
+        self.m2 += delta * (batch_mean - self.mean);
+        true
+    }
+}
+
+/// A user-provided set of inputs with their groups, which is cycled through by the measurement runs.
+struct Corpus<I> {
+    inputs: Vec<I>,
+    is_group_a: Vec<bool>,
+    /// The index of the corpus entry that is used for the next computation.
+    next_index: usize,
+}
+
+/// A pool of distinct inputs that are referenced by index for each computation of a measurement run.
+struct InputPool<I> {
+    inputs: Vec<I>,
+    /// The index into `inputs` for each computation of a measurement run.
+    indices: Vec<usize>,
+    /// The indices into `inputs` of group a and b, of which one is picked at random for each computation.
+    group_indices: [Vec<usize>; 2],
+}
+
+/// The verdict of a measurement run together with the statistics it is based on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeasurementRunResult {
+    LeakageFound(MeasurementReport),
+    NoLeakageEvidenceYet(MeasurementReport),
+    /// A stop condition was reached without finding leakage, so no more runs are required.
+    NoLeakageEvidence(MeasurementReport),
+    /// The difference of the mean execution times is within the equivalence margin at 95% confidence,
+    /// so no more runs are required.
+    ConstantTimeWithinMargin(MeasurementReport),
+    /// The timer could not resolve the execution times, so no verdict can be given.
+    InsufficientTimerResolution(MeasurementReport),
+}
+
+impl MeasurementRunResult {
+    /// Returns the statistics of the measurement run.
+    pub fn report(&self) -> &MeasurementReport {
+        match self {
+            MeasurementRunResult::LeakageFound(report)
+            | MeasurementRunResult::NoLeakageEvidenceYet(report)
+            | MeasurementRunResult::NoLeakageEvidence(report)
+            | MeasurementRunResult::ConstantTimeWithinMargin(report)
+            | MeasurementRunResult::InsufficientTimerResolution(report) => report,
         }
     }
 
-    fn measure(&mut self) {
-        self.first_tick = cpu_ticks();
-        for i in 0..self.number_of_computations_per_run {
-            T::do_one_computation(self.input_data[i]);
-            self.ticks[i] = cpu_ticks();
+    /// Returns true, if no verdict was reached yet and more measurement runs are required.
+    pub fn is_no_leakage_evidence_yet(&self) -> bool {
+        matches!(self, MeasurementRunResult::NoLeakageEvidenceYet(_))
+    }
+
+    /// Returns the result of a measurement run that found leakage or requires more measurement runs.
+    fn graded(leakage_found: bool, report: MeasurementReport) -> Self {
+        if leakage_found {
+            MeasurementRunResult::LeakageFound(report)
+        } else {
+            MeasurementRunResult::NoLeakageEvidenceYet(report)
         }
-        for i in 0..self.ticks.len() {
-            let previous_tick = if i == 0 {
-                self.first_tick
-            } else {
-                self.ticks[i - 1]
+    }
+}
+
+/// The final verdict of a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The maximum t value exceeds the overwhelming threshold.
+    DefinitelyNotConstantTime,
+    /// The maximum t value exceeds the moderate threshold.
+    ProbablyNotConstantTime,
+    /// No leakage was found with the executed measurements.
+    MaybeConstantTime,
+    /// The difference of the mean execution times is within the equivalence margin at 95% confidence.
+    ConstantTimeWithinMargin,
+    /// The timer could not resolve the execution times, so no verdict can be given.
+    InsufficientTimerResolution,
+}
+
+/// The final report of a test, e.g. for asserting on the result or processing it with other tools.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DudectReport {
+    pub verdict: Verdict,
+    /// The test that yielded the maximum t value.
+    pub max_test: TestKind,
+    /// The number of measurements of the test that yielded the maximum t value.
+    pub number_of_measurements: f64,
+    /// The maximum absolute t value of all tests (the z value for the Mann-Whitney U test
+    /// and the statistic for custom tests).
+    pub max_t: f64,
+    /// The maximum t value normalized by the square root of the number of measurements.
+    pub max_tau: f64,
+    /// The estimate (5/tau)^2 of the number of measurements required to detect the leakage.
+    /// It is `None` if no difference was measured, as the estimate is infinite then.
+    pub required_measurements: Option<f64>,
+    /// The two-sided p-value of the test that yielded the maximum t value, if it can be computed.
+    pub p_value: Option<f64>,
+    /// The statistics of all tests.
+    pub statistics: CampaignSnapshot,
+}
+
+/// The statistics of a measurement run, which are based on the test that yielded the maximum t value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasurementReport {
+    /// The test that yielded the maximum t value.
+    pub max_test: TestKind,
+    /// The number of measurements of the test that yielded the maximum t value.
+    pub number_of_measurements: f64,
+    /// The maximum absolute t value of all tests (the z value for the Mann-Whitney U test
+    /// and the statistic for custom tests).
+    pub max_t: f64,
+    /// The maximum t value normalized by the square root of the number of measurements.
+    pub max_tau: f64,
+    /// The configured t value above which a leakage is definitely not constant time.
+    pub t_threshold_overwhelming: f64,
+    /// The Welch-Satterthwaite degrees of freedom of the test that yielded the maximum t value,
+    /// if it can be computed. It is `None` for the robust and the Mann-Whitney U test, and for custom tests without one.
+    pub degrees_of_freedom: Option<f64>,
+    /// The two-sided p-value of the test that yielded the maximum t value (not corrected for the number of tests),
+    /// if it can be computed. It is `None` for the robust test and for custom tests without one.
+    pub p_value: Option<f64>,
+    /// The smallest p-value of all tests corrected for the number of tests, if a significance level is set.
+    pub corrected_p_value: Option<f64>,
+    /// The name and the statistic of each test that was added with `MeasurementContext::with_statistical_test`.
+    pub custom_statistics: Vec<(&'static str, Option<f64>)>,
+    /// The 95% confidence interval of the difference of the mean execution times (group a - group b) in ticks,
+    /// if there are enough measurements.
+    pub mean_difference_interval: Option<(f64, f64)>,
+    /// The number of executed computations per second of wall-clock time.
+    pub throughput: f64,
+    /// The name of the cycle counter that measured the execution times.
+    pub cycle_counter: &'static str,
+    /// The absolute t value of the first-order uncropped test on the wall-clock execution times,
+    /// if the wall-clock measurement is enabled.
+    pub wall_clock_t: Option<f64>,
+    /// Whether the wall-clock t value exceeds the moderate threshold, if it can be computed.
+    pub wall_clock_leakage_found: Option<bool>,
+    /// Whether the cycle counter (max t above the moderate threshold) and the wall clock disagree on leakage,
+    /// if the wall-clock t value can be computed. A disagreement hints at a problem of the cycle counter, e.g. the TSC.
+    pub wall_clock_disagreement: Option<bool>,
+    /// The overlapping coefficient of the execution times of both groups in the reservoir (1 means identical
+    /// distributions), if the reservoir is enabled.
+    pub overlap_coefficient: Option<f64>,
+    /// The statistic and the p-value of the Kolmogorov-Smirnov test over all analyzed execution times,
+    /// if the test is enabled.
+    pub kolmogorov_smirnov: Option<(f64, f64)>,
+    /// The standardized statistic and the approximate p-value of the Anderson-Darling test over all analyzed
+    /// execution times, if the test is enabled.
+    pub anderson_darling: Option<(f64, f64)>,
+    /// The estimated mutual information between the group and the binned execution time in bits, if it is enabled.
+    pub mutual_information: Option<f64>,
+    /// The measured overhead of the timer and the measurement loop, if the calibration is enabled.
+    pub timer_calibration: Option<TimerCalibration>,
+    /// The number of computations of the same input that are covered by each execution time.
+    pub computations_per_measurement: usize,
+    /// The number of execution times of the analyzed measurement runs that were not used for the statistics.
+    pub discarded_samples: usize,
+    /// The environment in which the measurements were executed.
+    pub environment: EnvironmentInfo,
+}
+
+/// The overhead of the timer and the measurement loop in ticks, measured without a computation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimerCalibration {
+    /// The minimum overhead, which is subtracted from each execution time.
+    pub min: u64,
+    /// The median overhead.
+    pub median: u64,
+}
+
+/// The complete state of the statistics of a measurement campaign, e.g. for an offline analysis.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CampaignSnapshot {
+    pub first_order_uncropped_test: TTest,
+    /// The percentile tests in the same order as `percentiles`.
+    pub percentile_tests: Vec<TTest>,
+    pub second_order_test: TTest,
+    /// The tests of the orders 3, 4, ..., which are empty if higher orders are not enabled.
+    pub higher_order_tests: Vec<TTest>,
+    /// The cropping thresholds of the percentile tests.
+    pub percentiles: Vec<u64>,
+    /// The number of executed measurement runs, including the warm-up.
+    pub runs_executed: usize,
+}
+
+impl CampaignSnapshot {
+    /// Returns the t value, the number of samples, and the means of every test, e.g. for plotting all tests
+    /// instead of only the maximum: the first-order uncropped test, the percentile tests, the second-order test,
+    /// and the higher-order tests, in this order.
+    pub fn summaries(&self) -> Vec<TestSummary> {
+        let summary = |kind, test: &TTest| TestSummary {
+            kind,
+            t: test.compute(),
+            number_of_samples: test.get_number_of_samples(),
+            mean: test.get_mean(),
+        };
+        let mut summaries =
+            Vec::with_capacity(self.percentile_tests.len() + self.higher_order_tests.len() + 2);
+        summaries.push(summary(
+            TestKind::FirstOrderUncropped,
+            &self.first_order_uncropped_test,
+        ));
+        for (index, test) in self.percentile_tests.iter().enumerate() {
+            let kind = TestKind::Percentile {
+                index,
+                threshold: self.percentiles.get(index).copied().unwrap_or(0),
             };
-            let current_tick = self.ticks[i];
-            // Note: wrapping might occur when the CPU counter overflows
-            self.execution_times[i] = current_tick - previous_tick;
+            summaries.push(summary(kind, test));
         }
+        summaries.push(summary(TestKind::SecondOrder, &self.second_order_test));
+        for (index, test) in self.higher_order_tests.iter().enumerate() {
+            summaries.push(summary(TestKind::HigherOrder(index + 3), test));
+        }
+        summaries
     }
+}
 
-    /// Prepare the percentiles with the values of the execution times as a baseline.
-    /// From dudect:
-    /// set different thresholds for cropping measurements.
-    /// the exponential tendency is meant to approximately match
-    /// the measurements distribution, but there's not more science
-    /// than that.
-    fn prepare_percentiles(&mut self) {
-        for i in 0..self.percentiles.len() {
-            self.percentiles[i] = percentile(
-                &mut self.execution_times,
-                1.0 - (f64::powf(0.5, 10.0 * (i as f64 + 1.0) / self.percentiles.len() as f64)),
-            );
+/// The state of a measurement campaign from which it can be resumed with `MeasurementContext::resume_from_checkpoint`.
+/// The optional tests, the histogram, and the reservoir are not part of it and start empty after resuming.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    /// The configuration with the current number of computations per run and number of percentiles.
+    pub config: DudectConfig,
+    /// The t-test accumulators, the cropping thresholds, and the number of executed measurement runs.
+    pub statistics: CampaignSnapshot,
+    /// The sketch of the cropping thresholds, if the streaming percentiles are enabled.
+    pub streaming_percentiles: Option<QuantileSketch>,
+    /// Whether the percentiles are prepared again from the next measurement run.
+    pub percentiles_outdated: bool,
+    /// The number of analyzed measurement runs since the percentiles were prepared.
+    pub runs_since_percentiles: usize,
+    /// The seed of the group assignment and the execution order, if set (see `MeasurementContext::with_seed`).
+    pub seed: Option<u64>,
+    pub computations_per_measurement: usize,
+    pub timer_calibration: Option<TimerCalibration>,
+    /// The number of warm-up runs that were completed.
+    pub warmup_runs_completed: usize,
+    /// The number of computations that were executed for all measurement runs, including the warm-up.
+    pub computations_executed: usize,
+    /// The number of execution times that were not used for the statistics.
+    pub discarded_samples: usize,
+    /// The wall-clock time since the first measurement run was started.
+    pub elapsed: Duration,
+    /// The significance level of the verdict, if set (see `MeasurementContext::with_alpha`).
+    pub alpha: Option<f64>,
+    pub multiple_testing_correction: MultipleTestingCorrection,
+    /// Whether the first-order uncropped test is considered for the verdict.
+    pub first_order_uncropped_enabled: bool,
+    /// Whether the second-order test is considered for the verdict.
+    pub second_order_enabled: bool,
+    /// The number of execution times at the start and the end of each measurement run that are not used for the statistics.
+    pub discarded_leading_samples: usize,
+    pub discarded_trailing_samples: usize,
+    /// Whether execution times equal to a cropping threshold are included in the percentile tests.
+    pub inclusive_crop: bool,
+    /// The running statistics of the accepted measurement runs, if the batch anomaly rejection is enabled.
+    pub batch_anomaly_rejection: Option<BatchAnomalyRejection>,
+}
+
+/// Identifies one of the tests of a measurement context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TestKind {
+    /// The first-order test on all analyzed execution times.
+    FirstOrderUncropped,
+    /// The first-order test on the execution times below the cropping `threshold` of the percentile with `index`.
+    Percentile { index: usize, threshold: u64 },
+    /// The second-order test on the centered products.
+    SecondOrder,
+    /// The test of the given order (3 or larger) on the standardized samples.
+    HigherOrder(usize),
+    /// The robust first-order test based on the median.
+    Robust,
+    /// The Mann-Whitney U test.
+    MannWhitney,
+    /// The test with the given index that was added with `MeasurementContext::with_statistical_test`.
+    Custom(usize),
+    /// The paired t-test of a paired measurement context.
+    Paired,
+}
+
+impl fmt::Display for TestKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestKind::FirstOrderUncropped => write!(f, "first order uncropped"),
+            TestKind::Percentile { index, threshold } => {
+                write!(f, "percentile {} (< {})", index, threshold)
+            }
+            TestKind::SecondOrder => write!(f, "second order"),
+            TestKind::HigherOrder(order) => write!(f, "order {}", order),
+            TestKind::Robust => write!(f, "robust"),
+            TestKind::MannWhitney => write!(f, "mann-whitney"),
+            TestKind::Custom(index) => write!(f, "custom {}", index),
+            TestKind::Paired => write!(f, "paired"),
         }
     }
+}
 
-    fn update_statistics(&mut self) {
-        // discard the first few measurements
-        for i in 10..self.number_of_computations_per_run - 1 {
-            let difference = self.execution_times[i] as f64;
+/// The statistics of a single t-test of a measurement context.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestSummary {
+    pub kind: TestKind,
+    /// The t value, if it can be computed.
+    pub t: Option<f64>,
+    /// The number of samples of group a and b.
+    pub number_of_samples: [f64; 2],
+    /// The means of group a and b.
+    pub mean: [f64; 2],
+}
 
-            // t-test on the execution time
-            self.first_order_uncropped_test
-                .push(difference, self.is_group_a[i]);
+/// The correction of the smallest p-value of several tests for the number of tests (family-wise error rate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MultipleTestingCorrection {
+    /// `min(p * m, 1)` for `m` tests, which is valid for dependent tests, but conservative (default).
+    Bonferroni,
+    /// `1 - (1 - p)^m` for `m` tests, which is exact for independent tests and slightly less conservative.
+    Sidak,
+}
 
-            // t-test on cropped execution times, for several cropping thresholds
-            for crop_index in 0..self.percentiles.len() {
-                if difference < self.percentiles[crop_index] as f64 {
-                    self.percentile_tests[crop_index].push(difference, self.is_group_a[i]);
-                }
-            }
+impl Default for MultipleTestingCorrection {
+    fn default() -> Self {
+        Self::Bonferroni
+    }
+}
 
-            // second-order test (only if we have more than 10000 measurements).
-            // Centered product pre-processing.
-            if self.percentile_tests[0].get_number_of_samples()[0] > 10000.0 {
-                let group_index = if self.is_group_a[i] { 0 } else { 1 };
-                let centered = difference - self.percentile_tests[0].get_mean()[group_index];
-                self.second_order_test
-                    .push(centered * centered, self.is_group_a[i]);
-            }
+impl MultipleTestingCorrection {
+    /// Returns the p-value `p` of the best of `number_of_tests` tests corrected for the number of tests.
+    pub fn correct(self, p: f64, number_of_tests: usize) -> f64 {
+        let m = number_of_tests as f64;
+        match self {
+            Self::Bonferroni => f64::min(p * m, 1.0),
+            // computed with ln_1p and exp_m1, as 1 - p is rounded to 1 for very small p
+            Self::Sidak => -f64::exp_m1(m * f64::ln_1p(-p)),
         }
     }
+}
 
-    fn report(&mut self) -> MeasurementRunResult {
-        let t = self.max_test();
-        let max_t = f64::abs(t.compute().unwrap_or(0.0));
-        let number_traces_max_t = {
-            let n = t.get_number_of_samples();
-            n[0] + n[1]
+/// How an execution time is computed if the tick after a computation is smaller than the tick before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickOverflowPolicy {
+    /// The counter wrapped around, the execution time is the wrapping difference (default).
+    /// A wrapping difference that is implausibly large, i.e. of a counter that went backwards, is dropped.
+    Wrap,
+    /// The counter went backwards (e.g. after a migration to a core with an unsynchronized counter),
+    /// the execution time is dropped.
+    Discard,
+    /// Panic, e.g. for counters that must be monotonic.
+    Panic,
+}
+
+impl Default for TickOverflowPolicy {
+    fn default() -> Self {
+        Self::Wrap
+    }
+}
+
+impl TickOverflowPolicy {
+    /// Returns the execution time between the ticks `previous` and `current` according to the policy.
+    /// Returns `None` if the execution time is dropped: a smaller `current` tick with `TickOverflowPolicy::Discard`
+    /// and any difference larger than the largest integer that `f64` represents exactly, which is implausible.
+    pub fn execution_time(self, previous: u64, current: u64) -> Option<u64> {
+        let difference = if current >= previous {
+            current - previous
+        } else {
+            match self {
+                Self::Wrap => current.wrapping_sub(previous),
+                Self::Discard => return None,
+                Self::Panic => panic!(
+                    "the cycle counter went backwards from {} to {}",
+                    previous, current
+                ),
+            }
         };
-        let max_tau = max_t / f64::sqrt(number_traces_max_t);
+        Some(difference).filter(|difference| *difference <= MAX_EXECUTION_TIME)
+    }
+}
 
-        // print the number of measurements of the test that yielded max t.
-        // sometimes you can see this number go down - this can be confusing
-        // but can happen (different test)
-        print!("meas: {:>7.2} M, ", (number_traces_max_t / 1e6));
-        if number_traces_max_t < ENOUGH_MEASUREMENTS as f64 {
-            println!(
-                "not enough measurements ({} still to go).",
-                ENOUGH_MEASUREMENTS - (number_traces_max_t as usize)
-            );
-            return MeasurementRunResult::NoLeakageEvidenceYet;
-        }
+/// The test used for comparing the execution times against a reference distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftTest {
+    /// Two-sample Kolmogorov-Smirnov test, which detects any change of the distribution.
+    KolmogorovSmirnov,
+    /// Welch's t-test, which detects a change of the mean.
+    TTest,
+}
 
-        /*
-         * We report the following statistics:
-         *
-         * max_t: the t value
-         * max_tau: a t value normalized by sqrt(number of measurements).
-         *          this way we can compare max_tau taken with different
-         *          number of measurements. This is sort of "distance
-         *          between distributions", independent of number of
-         *          measurements.
-         * (5/tau)^2: how many measurements we would need to barely
-         *            detect the leak, if present. "barely detect the
-         *            leak" here means have a t value greater than 5.
-         *
-         * The first metric is standard; the other two aren't (but
-         * pretty sensible imho)
-         */
+/// The result of comparing the execution times against a reference distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DriftResult {
+    /// The statistic of the test: the KS distance or the absolute t value.
+    pub statistic: f64,
+    /// The p-value of the test, if it could be computed.
+    pub p_value: Option<f64>,
+    /// True, if the p-value is below 1e-5, i.e. the execution times differ from the reference.
+    pub drifted: bool,
+}
 
-        print!(
-            "max t: {:>7.2}, max tau: {:.2e}, (5/tau)^2: {:.2e}.",
-            max_t,
-            max_tau,
-            (5.0 * 5.0) / (max_tau * max_tau)
+impl<T: MeasurementSpecimen> MeasurementContext<T> {
+    /// Create a new measurement context with the provided data.
+    ///
+    /// Panics if the number of percentiles of the configuration is 0.
+    pub fn new(specimen: T, config: DudectConfig) -> Self {
+        assert!(
+            config.number_of_percentiles > 0,
+            "at least one percentile test is required"
         );
-        if max_t > TTEST_FAILED_OVERWHELMINGLY {
-            println!(" Definitely not constant time.");
-            return MeasurementRunResult::LeakageFound;
-        }
-        if max_t > TTEST_FAILED_MODERATE {
-            println!(" Probably not constant time.");
-            return MeasurementRunResult::LeakageFound;
-        } else {
-            println!(" For the moment, maybe constant time.");
+        let number_of_computations_per_run = config.number_of_computations_per_run;
+        Self {
+            specimen,
+            cycle_counter: default_cycle_counter(),
+            first_tick: 0,
+            ticks: vec![0; number_of_computations_per_run],
+            number_of_computations_per_run,
+            computations_per_measurement: 1,
+            auto_computations_per_measurement: DEFAULT_AUTO_COMPUTATIONS_PER_MEASUREMENT,
+            repetitions_per_input: 1,
+            tick_overflow_policy: TickOverflowPolicy::default(),
+            tick_overflows: 0,
+            dropped: vec![false; number_of_computations_per_run],
+            outputs: None,
+            overhead_calibration_enabled: false,
+            timer_calibration: None,
+            enough_measurements: config.enough_measurements,
+            t_threshold_moderate: config.t_threshold_moderate,
+            t_threshold_overwhelming: config.t_threshold_overwhelming,
+            max_measurements: config.max_measurements,
+            max_duration: config.max_duration,
+            execution_times: vec![0; number_of_computations_per_run],
+            first_order_uncropped_test: TTest::new(),
+            first_order_uncropped_enabled: true,
+            windowed_first_order_test: None,
+            robust_test: None,
+            mann_whitney_test: None,
+            custom_tests: Vec::new(),
+            segmented: None,
+            batch_anomaly_rejection: None,
+            wall_clock: None,
+            reservoir: None,
+            histogram: None,
+            kolmogorov_smirnov_enabled: false,
+            anderson_darling_enabled: false,
+            mutual_information_enabled: false,
+            percentile_tests: vec![TTest::new(); config.number_of_percentiles],
+            second_order_test: TTest::new(),
+            second_order_enabled: true,
+            second_order_min_samples: SECOND_ORDER_MIN_SAMPLES,
+            higher_order_tests: Vec::new(),
+            alpha: None,
+            equivalence_margin: None,
+            multiple_testing_correction: MultipleTestingCorrection::default(),
+            input_data: vec![T::Input::initial(); number_of_computations_per_run],
+            initial_input: T::Input::initial(),
+            is_group_a: vec![false; number_of_computations_per_run],
+            percentiles: vec![0u64; config.number_of_percentiles],
+            percentile_curve_exponent: config.percentile_curve_exponent,
+            auto_batch_duration: None,
+            percentiles_outdated: false,
+            streaming_percentiles: None,
+            percentile_reestimation_interval: None,
+            runs_since_percentiles: 0,
+            static_inputs: false,
+            inputs_prepared: false,
+            input_pool: None,
+            corpus: None,
+            execution_order: None,
+            shuffled_execution: false,
+            separate_passes: false,
+            rng: default_rng(),
+            seed: None,
+            max_zero_delta_fraction: MAX_ZERO_DELTA_FRACTION,
+            inclusive_crop: false,
+            input_grouping: None,
+            discarded_leading_samples: DISCARDED_LEADING_SAMPLES,
+            discarded_trailing_samples: DISCARDED_TRAILING_SAMPLES,
+            discarded_samples: 0,
+            warmup_runs: 1,
+            warmup_runs_completed: 0,
+            runs_executed: 0,
+            computations_executed: 0,
+            clock: MonotonicClock::new(),
+            start_time: None,
+            elapsed_before_resume: Duration::ZERO,
+            environment: None,
+            reporter: default_reporter(),
+            last_report: None,
+            #[cfg(feature = "std")]
+            batch_recorder: None,
+            #[cfg(feature = "std")]
+            csv_export: None,
+            history: None,
         }
-        MeasurementRunResult::NoLeakageEvidenceYet
     }
 
-    /// Find the t-test with the maximum t value of `self.first_order_uncropped_test`, `self.percentile_tests`, and `self.second_order_test`.
-    fn max_test(&self) -> TTest {
-        fn max_test_function(a: &&TTest, b: &&TTest) -> Ordering {
-            let a_value = a.compute().unwrap_or(0.0);
-            let b_value = b.compute().unwrap_or(0.0);
-            f64::partial_cmp(&a_value, &b_value).unwrap()
-        }
+    /// Create a new measurement context whose input buffers are filled with `initial_input` instead of
+    /// `MeasurementInput::initial`, e.g. heap-allocated inputs with a length that is chosen at runtime.
+    /// The buffers are allocated once, so `MeasurementSpecimen::prepare_input_data` can overwrite them in place.
+    pub fn from_initial_input(specimen: T, config: DudectConfig, initial_input: T::Input) -> Self {
+        let mut context = Self::new(specimen, config);
+        context.input_data = vec![initial_input.clone(); context.number_of_computations_per_run];
+        context.initial_input = initial_input;
+        context
+    }
 
-        let mut max_test = *self
-            .percentile_tests
+    /// Create a new measurement context that cycles through the provided inputs and groups (true for group a)
+    /// instead of generating them, e.g. for replaying captured inputs.
+    ///
+    /// Panics if the corpus is empty or the number of inputs and groups differ.
+    pub fn from_inputs(
+        specimen: T,
+        config: DudectConfig,
+        inputs: Vec<T::Input>,
+        is_group_a: Vec<bool>,
+    ) -> Self {
+        assert!(!inputs.is_empty(), "the corpus must not be empty");
+        assert_eq!(
+            inputs.len(),
+            is_group_a.len(),
+            "the corpus must have a group for each input"
+        );
+        let mut context = Self::new(specimen, config);
+        context.corpus = Some(Corpus {
+            inputs,
+            is_group_a,
+            next_index: 0,
+        });
+        context
+    }
+
+    /// Generate the input data and the group assignment only once and reuse them for every measurement run.
+    /// This reduces the diversity of the inputs, so a leakage of rare inputs might not be detected.
+    #[must_use]
+    pub fn with_static_inputs(mut self) -> Self {
+        self.static_inputs = true;
+        self
+    }
+
+    /// Use a pool of distinct inputs: each computation of group a uses an input of `inputs` at one of the indices
+    /// `group_a`, picked at random, and each computation of group b one at the indices `group_b`.
+    /// Only an index is stored per computation and `MeasurementSpecimen::prepare_input_data` is not called.
+    ///
+    /// Panics if the indices of a group are empty or out of range of `inputs`.
+    #[must_use]
+    pub fn with_input_pool(
+        mut self,
+        inputs: Vec<T::Input>,
+        group_a: Vec<usize>,
+        group_b: Vec<usize>,
+    ) -> Self {
+        assert!(
+            !group_a.is_empty() && !group_b.is_empty(),
+            "each group requires at least one input of the pool"
+        );
+        if let Some(index) = group_a
             .iter()
-            .max_by(max_test_function)
-            .unwrap();
-        if max_test_function(&&max_test, &&self.first_order_uncropped_test) == Ordering::Less {
-            max_test = self.first_order_uncropped_test;
-        }
-        if max_test_function(&&max_test, &&self.second_order_test) == Ordering::Less {
-            max_test = self.second_order_test;
+            .chain(&group_b)
+            .find(|index| **index >= inputs.len())
+        {
+            panic!(
+                "input index {} is out of range for a pool of size {}",
+                index,
+                inputs.len()
+            );
         }
-        max_test
+        self.input_data = Vec::new();
+        self.input_pool = Some(InputPool {
+            inputs,
+            indices: vec![0; self.number_of_computations_per_run],
+            group_indices: [group_a, group_b],
+        });
+        self
     }
-}
 
-fn percentile(data: &mut [u64], which: f64) -> u64 {
-    // it is not important for the sorting to keep the order of equal elements
-    data.sort_unstable();
+    /// Derive the group of each computation from its prepared input with `is_group_a` instead of a random assignment,
+    /// e.g. for fixed-vs-fixed designs.
+    #[must_use]
+    pub fn with_input_grouping(mut self, is_group_a: fn(&T::Input) -> bool) -> Self {
+        self.input_grouping = Some(is_group_a);
+        self
+    }
 
-    let array_position = (data.len() as f64 * which) as usize;
-    assert!(array_position < data.len());
-    data[array_position]
-}
+    /// Execute the computations of each measurement run in a newly shuffled order,
+    /// which decorrelates the position of a computation from its input and group.
+    #[must_use]
+    pub fn with_shuffled_execution(mut self) -> Self {
+        self.execution_order = Some((0..self.number_of_computations_per_run).collect());
+        self.shuffled_execution = true;
+        self
+    }
 
-/// Executes a function for testing and runs as long as required.
-pub fn run_dudect_test<T: MeasurementSpecimen<N>, const N: usize>(specimen: T) {
-    let mut dudect = MeasurementContext::new(specimen, 500);
-    let mut result = MeasurementRunResult::NoLeakageEvidenceYet;
-    while result == MeasurementRunResult::NoLeakageEvidenceYet {
-        result = dudect.execute_measurement_run();
-    }
-}
-
-/// Returns the current CPU ticks count. From the dudect implementation:
-/// Intel actually recommends calling CPUID to serialize the execution flow
-/// and reduce variance in measurement due to out-of-order execution.
-/// We don't do that here yet.
-/// see §3.2.1 http://www.intel.com/content/www/us/en/embedded/training/ia-32-ia-64-benchmark-code-execution-paper.html
-pub fn cpu_ticks() -> u64 {
-    let upper: u64;
-    let lower: u64;
-    unsafe {
-        asm!("rdtsc", out("rax") lower, out("rdx") upper);
+    /// Derive the group assignment and the execution order from `seed` and the number of the measurement run,
+    /// so a campaign can be repeated. The inputs that are prepared by the specimen are not affected.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Execute all computations of group a before the computations of group b instead of interleaving them.
+    /// The groups are then no longer exposed to the same drift of the machine.
+    #[must_use]
+    pub fn with_separate_passes(mut self) -> Self {
+        self.execution_order = Some((0..self.number_of_computations_per_run).collect());
+        self.separate_passes = true;
+        self
+    }
+
+    /// Returns the order of the input indices in which the computations of the last run were executed.
+    /// Returns `None` if the inputs are executed in order.
+    pub fn execution_order(&self) -> Option<&[usize]> {
+        self.execution_order.as_deref()
+    }
+
+    /// Set the maximum fraction of zero execution times among the analyzed samples of a run (0.5 by default),
+    /// above which the run results in `MeasurementRunResult::InsufficientTimerResolution`.
+    #[must_use]
+    pub fn with_max_zero_delta_fraction(mut self, fraction: f64) -> Self {
+        self.max_zero_delta_fraction = fraction;
+        self
+    }
+
+    /// Include execution times that are equal to a cropping threshold in the percentile tests (`<=` instead of `<`),
+    /// which fills the lower percentile tests of a coarse timer.
+    #[must_use]
+    pub fn with_inclusive_crop(mut self, inclusive: bool) -> Self {
+        self.inclusive_crop = inclusive;
+        self
+    }
+
+    /// Enable or disable the first-order uncropped test (enabled by default).
+    /// A disabled test does not collect samples and is not considered for the verdict.
+    #[must_use]
+    pub fn with_first_order_uncropped(mut self, enabled: bool) -> Self {
+        self.first_order_uncropped_enabled = enabled;
+        self
+    }
+
+    /// Enable or disable the second-order test (enabled by default).
+    /// Disabling it speeds up the statistics update, but only first-order leakage can be detected.
+    #[must_use]
+    pub fn with_second_order(mut self, enabled: bool) -> Self {
+        self.second_order_enabled = enabled;
+        self
+    }
+
+    /// Set the number of samples of the first-order uncropped test that are required
+    /// before the second-order test is updated (defaults to 10000).
+    #[must_use]
+    pub fn with_second_order_min_samples(mut self, min_samples: usize) -> Self {
+        self.second_order_min_samples = min_samples as f64;
+        self
+    }
+
+    /// Additionally track tests of the orders 3 up to `max_order` on the standardized samples and consider them
+    /// for the verdict, e.g. for masked implementations. They start with the second-order test.
+    ///
+    /// Panics if `max_order` is smaller than 2.
+    #[must_use]
+    pub fn with_max_order(mut self, max_order: usize) -> Self {
+        assert!(max_order >= 2, "the maximum order must be at least 2");
+        self.higher_order_tests = vec![TTest::new(); max_order - 2];
+        self
+    }
+
+    /// Returns the t values of the higher-order tests, starting with the order 3.
+    /// The slice is empty if no higher orders are enabled (see `with_max_order`).
+    pub fn higher_order_t_values(&self) -> Vec<Option<f64>> {
+        self.higher_order_tests
+            .iter()
+            .map(|test| test.compute())
+            .collect()
+    }
+
+    /// Certify constant time within `margin` ticks with two one-sided t-tests at 95% confidence,
+    /// which return `MeasurementRunResult::ConstantTimeWithinMargin` if no leakage was found.
+    #[must_use]
+    pub fn with_equivalence_margin(mut self, margin: f64) -> Self {
+        self.equivalence_margin = Some(margin);
+        self
+    }
+
+    /// Returns true, if an equivalence margin is set and the difference of the mean execution times is shown to be
+    /// within it at 95% confidence with enough measurements.
+    pub fn is_within_equivalence_margin(&self) -> bool {
+        let margin = match self.equivalence_margin {
+            Some(margin) => margin,
+            None => return false,
+        };
+        // two one-sided tests at level alpha are equivalent to a (1 - 2 alpha) confidence interval
+        self.has_enough_measurements()
+            && self
+                .mean_difference_interval(2.0 * EQUIVALENCE_CONFIDENCE_LEVEL - 1.0)
+                .map_or(false, |(lower, upper)| -margin < lower && upper < margin)
+    }
+
+    /// Decide on leakage with the significance level `alpha` (e.g. `1e-5`) instead of the fixed t value thresholds.
+    /// Leakage is found, if the smallest p-value of all tests, corrected for the number of tests
+    /// (with the Bonferroni method by default, see `with_multiple_testing_correction`), is below `alpha`.
+    #[must_use]
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    /// Set the method that corrects the smallest p-value for the number of tests, if a significance level is set
+    /// with `with_alpha`. Taking the maximum of many tests inflates the false-positive rate, which the correction
+    /// compensates.
+    #[must_use]
+    pub fn with_multiple_testing_correction(
+        mut self,
+        correction: MultipleTestingCorrection,
+    ) -> Self {
+        self.multiple_testing_correction = correction;
+        self
+    }
+
+    /// Additionally track a first-order uncropped test that only considers the last `window_size` samples per group.
+    /// A windowed t value that diverges from the cumulative one hints at an unstable environment.
+    #[must_use]
+    pub fn with_windowed_test(mut self, window_size: usize) -> Self {
+        self.windowed_first_order_test = Some(WindowedTTest::new(window_size));
+        self
+    }
+
+    /// Additionally track a robust first-order test based on the median and consider it for the verdict.
+    /// It retains all measurements in memory.
+    #[must_use]
+    pub fn with_robust_test(mut self) -> Self {
+        self.robust_test = Some(RobustTTest::new());
+        self
+    }
+
+    /// Additionally track a nonparametric Mann-Whitney U test and consider its z value for the verdict.
+    /// The test compares the ranks of the execution times and does not assume normally distributed values,
+    /// but it retains all measurements in memory.
+    #[must_use]
+    pub fn with_mann_whitney_test(mut self) -> Self {
+        self.mann_whitney_test = Some(MannWhitneyUTest::new());
+        self
+    }
+
+    /// Additionally feed the analyzed execution times to `test`, e.g. a custom detector, and consider it for the verdict:
+    /// leakage is found, if its statistic exceeds its threshold. Can be called several times to add several tests.
+    #[must_use]
+    pub fn with_statistical_test<S: StatisticalTest + 'static>(mut self, test: S) -> Self {
+        self.custom_tests.push(Box::new(test));
+        self
+    }
+
+    /// Returns the name and the statistic of each test that was added with `with_statistical_test`.
+    pub fn custom_statistics(&self) -> Vec<(&'static str, Option<f64>)> {
+        self.custom_tests
+            .iter()
+            .map(|test| (test.name(), test.statistic()))
+            .collect()
+    }
+
+    /// Execute the computations with `MeasurementSpecimen::do_one_computation_segmented` and additionally track
+    /// a first-order uncropped test for each phase of the computation between the recorded marks.
+    /// Recording the marks adds overhead to the measured execution times.
+    ///
+    /// Panics if several computations or repetitions per measurement are set (e.g. by default on WebAssembly),
+    /// as a phase is a part of a single computation.
+    #[must_use]
+    pub fn with_segmented_computation(mut self) -> Self {
+        assert!(
+            self.computations_per_measurement == 1
+                && !self.auto_computations_per_measurement
+                && self.repetitions_per_input == 1,
+            "the segmented computation requires a single computation and repetition per measurement"
+        );
+        self.segmented = Some(SegmentedMeasurement {
+            marks: vec![Vec::new(); self.number_of_computations_per_run],
+            tests: Vec::new(),
+        });
+        self
+    }
+
+    /// Returns the t values of the tests of each phase of a segmented computation.
+    /// The result is empty, if the segmented measurement is not enabled.
+    pub fn segment_t_values(&self) -> Vec<Option<f64>> {
+        self.segmented
+            .as_ref()
+            .map(|segmented| segmented.tests.iter().map(|test| test.compute()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Discard a whole measurement run, if the mean of its analyzed execution times deviates more than `k`
+    /// standard deviations (at least 1% of their mean) from the means of the previously accepted runs.
+    /// Runs are only discarded after 5 accepted runs, and after 3 consecutive anomalous runs the current run is
+    /// accepted as the start of a new baseline, e.g. after a persistent change of the clock frequency.
+    #[must_use]
+    pub fn with_batch_anomaly_rejection(mut self, k: f64) -> Self {
+        self.batch_anomaly_rejection = Some(BatchAnomalyRejection::new(k));
+        self
+    }
+
+    /// Returns the number of measurement runs that were discarded because of an anomalous mean execution time.
+    pub fn rejected_batches(&self) -> usize {
+        self.batch_anomaly_rejection
+            .as_ref()
+            .map(|rejection| rejection.rejected_batches)
+            .unwrap_or(0)
+    }
+
+    /// Additionally measure the wall-clock time of each computation and track a first-order uncropped test on it.
+    /// Reading the clock adds a constant overhead to each measured computation. Requires `std`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn with_wall_clock(mut self) -> Self {
+        let now = self.clock.now().unwrap_or_default();
+        self.wall_clock = Some(WallClockMeasurement {
+            first_instant: now,
+            instants: vec![now; self.number_of_computations_per_run],
+            execution_times: vec![0; self.number_of_computations_per_run],
+            first_order_uncropped_test: TTest::new(),
+        });
+        self
+    }
+
+    /// Returns the t value of the first-order uncropped test on the wall-clock execution times.
+    /// Returns `None` if the wall-clock measurement is not enabled or the test does not have enough samples.
+    pub fn wall_clock_t(&self) -> Option<f64> {
+        self.wall_clock
+            .as_ref()
+            .and_then(|wall_clock| wall_clock.first_order_uncropped_test.compute())
+    }
+
+    /// Keep a uniform random sample of up to `size` analyzed execution times per group over all measurement runs.
+    /// In contrast to the execution times of the last run, the sample represents the whole measurement campaign,
+    /// e.g. for plotting the distributions.
+    #[must_use]
+    pub fn with_reservoir(mut self, size: usize) -> Self {
+        self.reservoir = Some(Reservoir::new(size));
+        self
+    }
+
+    /// Returns the sampled execution times of group a and b.
+    /// Both slices are empty if the reservoir is not enabled.
+    pub fn reservoir(&self) -> (&[u64], &[u64]) {
+        match &self.reservoir {
+            Some(reservoir) => reservoir.get_samples(),
+            None => (&[], &[]),
+        }
+    }
+
+    /// Report the two-sample Kolmogorov-Smirnov test of a histogram with bins of `bin_width` ticks next to max t.
+    /// The histogram is shared with the other distribution tests, so the bin width of the last call is used.
+    #[must_use]
+    pub fn with_kolmogorov_smirnov_test(mut self, bin_width: u64) -> Self {
+        self.histogram = Some(TwoSampleHistogram::new(bin_width));
+        self.kolmogorov_smirnov_enabled = true;
+        self
+    }
+
+    /// Returns the statistic and the p-value of the Kolmogorov-Smirnov test over all analyzed execution times,
+    /// if the test is enabled and both groups have samples.
+    pub fn kolmogorov_smirnov(&self) -> Option<(f64, f64)> {
+        self.histogram
+            .as_ref()
+            .filter(|_| self.kolmogorov_smirnov_enabled)
+            .and_then(TwoSampleHistogram::kolmogorov_smirnov)
+    }
+
+    /// Report the two-sample Anderson-Darling test of a histogram with bins of `bin_width` ticks next to max t.
+    /// The histogram is shared with the other distribution tests, so the bin width of the last call is used.
+    #[must_use]
+    pub fn with_anderson_darling_test(mut self, bin_width: u64) -> Self {
+        self.histogram = Some(TwoSampleHistogram::new(bin_width));
+        self.anderson_darling_enabled = true;
+        self
+    }
+
+    /// Returns the standardized statistic and the approximate p-value of the Anderson-Darling test over all
+    /// analyzed execution times, if the test is enabled and can be computed.
+    pub fn anderson_darling(&self) -> Option<(f64, f64)> {
+        self.histogram
+            .as_ref()
+            .filter(|_| self.anderson_darling_enabled)
+            .and_then(TwoSampleHistogram::anderson_darling)
+    }
+
+    /// Report the mutual information in bits between the group and the execution time binned by `bin_width` ticks.
+    /// The histogram is shared with the distribution tests, so the bin width of the last call is used.
+    #[must_use]
+    pub fn with_mutual_information(mut self, bin_width: u64) -> Self {
+        self.histogram = Some(TwoSampleHistogram::new(bin_width));
+        self.mutual_information_enabled = true;
+        self
+    }
+
+    /// Returns the estimated mutual information between the group and the binned execution time in bits,
+    /// if it is enabled and both groups have samples.
+    pub fn mutual_information(&self) -> Option<f64> {
+        self.histogram
+            .as_ref()
+            .filter(|_| self.mutual_information_enabled)
+            .and_then(TwoSampleHistogram::mutual_information)
+    }
+
+    /// Use `cycle_counter` for the timestamps of the measurements and the marks of the segmented computation instead
+    /// of the default counter of the architecture.
+    #[must_use]
+    pub fn with_cycle_counter<C: CycleCounter + 'static>(mut self, cycle_counter: C) -> Self {
+        self.cycle_counter = Box::new(cycle_counter);
+        self
+    }
+
+    /// Send the progress and the results of the measurement runs to `reporter` instead of printing them to stdout
+    /// (see [`ConsoleReporter`]), e.g. [`SilentReporter`] inside a test harness or a custom reporter for a GUI.
+    #[must_use]
+    pub fn with_reporter<R: Reporter + 'static>(mut self, reporter: R) -> Self {
+        self.reporter = Box::new(reporter);
+        self
+    }
+
+    /// Choose the number of computations per run after the warm-up, so that a run takes about `duration_per_run`.
+    /// It is limited to the range from 100 to 1000000. Requires `std`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn with_auto_batch(mut self, duration_per_run: Duration) -> Self {
+        self.auto_batch_duration = Some(duration_per_run);
+        self
+    }
+
+    /// Execute the computation `computations` times with the same input for each measurement (1 by default),
+    /// so that functions faster than the resolution of the timer can be measured. This replaces the automatic choice
+    /// (see `with_auto_computations_per_measurement`), which is the default on WebAssembly.
+    ///
+    /// The execution times then cover all computations of a measurement. The t values are not affected by this
+    /// scaling, but the execution times, percentiles, and timing resolution are in ticks per measurement;
+    /// the reports contain the number of computations per measurement for converting them to a single computation.
+    ///
+    /// Panics if `computations` is 0, or larger than 1 with the segmented computation.
+    #[must_use]
+    pub fn with_computations_per_measurement(mut self, computations: usize) -> Self {
+        assert!(
+            computations > 0,
+            "at least one computation per measurement is required"
+        );
+        assert!(
+            computations == 1 || self.segmented.is_none(),
+            "the segmented computation requires a single computation and repetition per measurement"
+        );
+        self.computations_per_measurement = computations;
+        self.auto_computations_per_measurement = false;
+        self
+    }
+
+    /// Double the number of computations per measurement before the warm-up run, until it takes at least
+    /// 100 granularities of the timer (up to 2^20), see `with_computations_per_measurement`.
+    ///
+    /// Panics with the segmented computation.
+    #[must_use]
+    pub fn with_auto_computations_per_measurement(mut self) -> Self {
+        assert!(
+            self.segmented.is_none(),
+            "the segmented computation requires a single computation and repetition per measurement"
+        );
+        self.auto_computations_per_measurement = true;
+        self
+    }
+
+    /// Set the number of warm-up runs (1 by default); the percentiles are prepared from the last one.
+    ///
+    /// Panics if `warmup_runs` is 0.
+    #[must_use]
+    pub fn with_warmup_runs(mut self, warmup_runs: usize) -> Self {
+        assert!(warmup_runs > 0, "at least one warm-up run is required");
+        self.warmup_runs = warmup_runs;
+        self
+    }
+
+    /// Set the number of execution times at the start (10 by default) and at the end (1 by default)
+    /// of each measurement run that are not used for the statistics.
+    ///
+    /// Panics if no execution time of a measurement run is left for the statistics.
+    #[must_use]
+    pub fn with_discarded_samples(mut self, leading: usize, trailing: usize) -> Self {
+        self.discarded_leading_samples = leading;
+        self.discarded_trailing_samples = trailing;
+        self.assert_analyzed_samples(self.number_of_computations_per_run);
+        self
+    }
+
+    /// Returns the number of execution times of all analyzed measurement runs that were not used for the statistics.
+    pub fn discarded_samples(&self) -> usize {
+        self.discarded_samples
+    }
+
+    /// Returns true, if the next measurement run is a warm-up run.
+    /// A warm-up run that results in `MeasurementRunResult::InsufficientTimerResolution` is repeated.
+    pub fn is_warming_up(&self) -> bool {
+        self.warmup_runs_completed < self.warmup_runs
+    }
+
+    /// Returns the number of computations of the same input that are covered by each execution time.
+    pub fn computations_per_measurement(&self) -> usize {
+        self.computations_per_measurement
+    }
+
+    /// Measure each input `repetitions` times in a row and use only the minimum execution time, which filters noise.
+    ///
+    /// Panics if `repetitions` is 0, or larger than 1 with the segmented computation.
+    #[must_use]
+    pub fn with_repetitions_per_input(mut self, repetitions: usize) -> Self {
+        assert!(
+            repetitions > 0,
+            "at least one repetition per input is required"
+        );
+        assert!(
+            repetitions == 1 || self.segmented.is_none(),
+            "the segmented computation requires a single computation and repetition per measurement"
+        );
+        self.repetitions_per_input = repetitions;
+        self
+    }
+
+    /// Set how an execution time is computed if the cycle counter returns a smaller tick after a computation than
+    /// before it (`TickOverflowPolicy::Wrap` by default).
+    #[must_use]
+    pub fn with_tick_overflow_policy(mut self, policy: TickOverflowPolicy) -> Self {
+        self.tick_overflow_policy = policy;
+        self
+    }
+
+    /// Returns the number of measurements of all runs for which the tick after the computation was smaller
+    /// than the tick before it, i.e. the counter wrapped around or went backwards.
+    /// The dropped execution times of the analyzed runs are counted in `discarded_samples` as well.
+    pub fn tick_overflows(&self) -> usize {
+        self.tick_overflows
+    }
+
+    /// Keep the output of the computation of each measurement and pass it to `MeasurementSpecimen::verify_output`
+    /// after the run. If several computations are executed per measurement, only the output of the last one is kept.
+    /// Storing the output adds a constant overhead to each measured execution time.
+    #[must_use]
+    pub fn with_output_verification(mut self) -> Self {
+        self.outputs = Some(Vec::with_capacity(self.number_of_computations_per_run));
+        self
+    }
+
+    /// Measure the overhead of the timer and the measurement loop before the warm-up run
+    /// and subtract its minimum from each execution time.
+    #[must_use]
+    pub fn with_overhead_calibration(mut self) -> Self {
+        self.overhead_calibration_enabled = true;
+        self
+    }
+
+    /// Returns the calibrated overhead of the timer, if the calibration is enabled and was executed.
+    pub fn timer_calibration(&self) -> Option<TimerCalibration> {
+        self.timer_calibration
+    }
+
+    /// Record the number of measurements, the maximum t value and the maximum tau after every measurement run
+    /// that reports a result, e.g. for plotting the convergence of the test.
+    #[must_use]
+    pub fn with_history(mut self) -> Self {
+        self.history = Some(Vec::new());
+        self
+    }
+
+    /// Returns the recorded (number of measurements, max t, max tau) for every reporting measurement run.
+    /// The slice is empty if the history is not enabled.
+    pub fn history(&self) -> &[(f64, f64, f64)] {
+        match &self.history {
+            Some(history) => history,
+            None => &[],
+        }
+    }
+
+    /// Write the analyzed execution times of every measurement run, including the warm-up, to `dir/batch_n.csv`
+    /// in the format of `with_csv_export`. Errors are reported, but do not stop the measurement.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn with_batch_recorder(mut self, dir: PathBuf) -> Self {
+        self.batch_recorder = Some(dir);
+        self
+    }
+
+    /// Stream the analyzed execution times of all measurement runs to `writer` as CSV (`run,execution_time,is_group_a`).
+    /// If writing fails, the error is reported and the export is stopped.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn with_csv_export<W: Write + 'static>(mut self, writer: W) -> Self {
+        let mut writer: Box<dyn Write> = Box::new(writer);
+        match writeln!(writer, "{}", crate::report::CSV_EXPORT_HEADER) {
+            Ok(()) => self.csv_export = Some(writer),
+            Err(error) => self
+                .reporter
+                .message(&format!("could not start the CSV export: {}", error)),
+        }
+        self
+    }
+
+    /// Returns the effective resolution of the timer, i.e. the smallest positive execution time
+    /// of the analyzed execution times of the last measurement run, or 0 if there is none.
+    pub fn timing_resolution(&self) -> u64 {
+        self.analyzed_times()
+            .filter(|time| *time > 0)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of computations that are executed for each measurement run.
+    pub fn number_of_computations_per_run(&self) -> usize {
+        self.number_of_computations_per_run
+    }
+
+    /// Derive the cropping thresholds again from the next measurement run and reset the percentile tests.
+    /// All other tests are kept.
+    pub fn recompute_percentiles(&mut self) {
+        self.percentile_tests = vec![TTest::new(); self.percentiles.len()];
+        self.percentiles_outdated = true;
+    }
+
+    /// Call `recompute_percentiles` after every `runs` analyzed measurement runs.
+    ///
+    /// Panics if `runs` is 0.
+    #[must_use]
+    pub fn with_percentile_reestimation(mut self, runs: usize) -> Self {
+        assert!(
+            runs > 0,
+            "the re-estimation interval must be at least one run"
+        );
+        self.percentile_reestimation_interval = Some(runs);
+        self
+    }
+
+    /// Estimate the cropping thresholds continuously from all analyzed execution times with a [`QuantileSketch`]
+    /// (at most 1/128 relative error) instead of only from the warm-up run.
+    #[must_use]
+    pub fn with_streaming_percentiles(mut self) -> Self {
+        self.streaming_percentiles = Some(QuantileSketch::new());
+        self
+    }
+
+    /// Compares the current execution times of both groups against `reference`, e.g. of a previous version.
+    /// The reservoir sample is used if it is enabled, otherwise the last measurement run.
+    pub fn compare_to_reference(&self, reference: &[u64], test: DriftTest) -> DriftResult {
+        let (reservoir_a, reservoir_b) = self.reservoir();
+        let current: Vec<u64> = if self.reservoir.is_some() {
+            reservoir_a.iter().chain(reservoir_b).copied().collect()
+        } else {
+            self.analyzed_times().collect()
+        };
+
+        let (statistic, p_value) = match test {
+            DriftTest::KolmogorovSmirnov => match kolmogorov_smirnov(&current, reference) {
+                Some((statistic, p_value)) => (statistic, Some(p_value)),
+                None => (0.0, None),
+            },
+            DriftTest::TTest => {
+                let mut t_test = TTest::new();
+                for time in &current {
+                    t_test.push(*time as f64, true);
+                }
+                for time in reference {
+                    t_test.push(*time as f64, false);
+                }
+                (f64::abs(t_test.compute().unwrap_or(0.0)), t_test.p_value())
+            }
+        };
+        DriftResult {
+            statistic,
+            p_value,
+            drifted: p_value.map_or(false, |p_value| p_value < DRIFT_ALPHA),
+        }
+    }
+
+    /// Returns the report of the last measurement run that updated the statistics.
+    /// Returns `None` if no such measurement run was executed yet.
+    pub fn last_report(&self) -> Option<&MeasurementReport> {
+        self.last_report.as_ref()
+    }
+
+    /// Returns the number of executed measurement runs, including the warm-up.
+    pub fn runs_executed(&self) -> usize {
+        self.runs_executed
+    }
+
+    /// Returns the final report of the measurement campaign for the `result` of its last measurement run.
+    pub fn final_report(&self, result: &MeasurementRunResult) -> DudectReport {
+        let report = result.report();
+        let verdict = match result {
+            MeasurementRunResult::LeakageFound(report)
+                if report.max_t > report.t_threshold_overwhelming =>
+            {
+                Verdict::DefinitelyNotConstantTime
+            }
+            MeasurementRunResult::LeakageFound(_) => Verdict::ProbablyNotConstantTime,
+            MeasurementRunResult::NoLeakageEvidenceYet(_)
+            | MeasurementRunResult::NoLeakageEvidence(_) => Verdict::MaybeConstantTime,
+            MeasurementRunResult::ConstantTimeWithinMargin(_) => Verdict::ConstantTimeWithinMargin,
+            MeasurementRunResult::InsufficientTimerResolution(_) => {
+                Verdict::InsufficientTimerResolution
+            }
+        };
+        DudectReport {
+            verdict,
+            max_test: report.max_test,
+            number_of_measurements: report.number_of_measurements,
+            max_t: report.max_t,
+            max_tau: report.max_tau,
+            required_measurements: Some((5.0 * 5.0) / (report.max_tau * report.max_tau))
+                .filter(|required_measurements| required_measurements.is_finite()),
+            p_value: report.p_value,
+            statistics: self.snapshot(),
+        }
+    }
+
+    /// Returns the state of all tests and the percentiles of the measurement campaign.
+    /// See `CampaignSnapshot::summaries` for the t value, the number of samples, and the means of every test.
+    pub fn snapshot(&self) -> CampaignSnapshot {
+        CampaignSnapshot {
+            first_order_uncropped_test: self.first_order_uncropped_test,
+            percentile_tests: self.percentile_tests.to_vec(),
+            second_order_test: self.second_order_test,
+            higher_order_tests: self.higher_order_tests.to_vec(),
+            percentiles: self.percentiles.to_vec(),
+            runs_executed: self.runs_executed,
+        }
+    }
+
+    /// Returns the state of the measurement campaign from which it can be resumed with `resume_from_checkpoint`,
+    /// e.g. after every measurement run of a long campaign.
+    pub fn save_checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            config: DudectConfig {
+                number_of_computations_per_run: self.number_of_computations_per_run,
+                enough_measurements: self.enough_measurements,
+                t_threshold_moderate: self.t_threshold_moderate,
+                t_threshold_overwhelming: self.t_threshold_overwhelming,
+                max_measurements: self.max_measurements,
+                max_duration: self.max_duration,
+                number_of_percentiles: self.percentiles.len(),
+                percentile_curve_exponent: self.percentile_curve_exponent,
+            },
+            statistics: self.snapshot(),
+            streaming_percentiles: self.streaming_percentiles.clone(),
+            percentiles_outdated: self.percentiles_outdated,
+            runs_since_percentiles: self.runs_since_percentiles,
+            seed: self.seed,
+            computations_per_measurement: self.computations_per_measurement,
+            timer_calibration: self.timer_calibration,
+            warmup_runs_completed: self.warmup_runs_completed,
+            computations_executed: self.computations_executed,
+            discarded_samples: self.discarded_samples,
+            elapsed: self.elapsed(),
+            alpha: self.alpha,
+            multiple_testing_correction: self.multiple_testing_correction,
+            first_order_uncropped_enabled: self.first_order_uncropped_enabled,
+            second_order_enabled: self.second_order_enabled,
+            discarded_leading_samples: self.discarded_leading_samples,
+            discarded_trailing_samples: self.discarded_trailing_samples,
+            inclusive_crop: self.inclusive_crop,
+            batch_anomaly_rejection: self.batch_anomaly_rejection,
+        }
+    }
+
+    /// Continue the measurement campaign of `checkpoint` (see `save_checkpoint`) with the same specimen and builders.
+    /// The configuration and the settings of the verdict of the checkpoint replace those of the builders.
+    ///
+    /// Panics if the checkpoint has no percentile tests, or their number does not match its number of percentiles.
+    #[must_use]
+    pub fn resume_from_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        let Checkpoint {
+            config, statistics, ..
+        } = checkpoint;
+        assert!(
+            config.number_of_percentiles > 0,
+            "at least one percentile test is required"
+        );
+        assert!(
+            config.number_of_percentiles == statistics.percentiles.len()
+                && statistics.percentiles.len() == statistics.percentile_tests.len(),
+            "the checkpoint requires one percentile test per percentile"
+        );
+        self.discarded_leading_samples = checkpoint.discarded_leading_samples;
+        self.discarded_trailing_samples = checkpoint.discarded_trailing_samples;
+        self.resize_runs(config.number_of_computations_per_run);
+        self.enough_measurements = config.enough_measurements;
+        self.t_threshold_moderate = config.t_threshold_moderate;
+        self.t_threshold_overwhelming = config.t_threshold_overwhelming;
+        self.max_measurements = config.max_measurements;
+        self.max_duration = config.max_duration;
+        self.percentile_curve_exponent = config.percentile_curve_exponent;
+        self.first_order_uncropped_test = statistics.first_order_uncropped_test;
+        self.percentile_tests = statistics.percentile_tests;
+        self.second_order_test = statistics.second_order_test;
+        self.higher_order_tests = statistics.higher_order_tests;
+        self.percentiles = statistics.percentiles;
+        self.runs_executed = statistics.runs_executed;
+        self.streaming_percentiles = checkpoint.streaming_percentiles;
+        self.percentiles_outdated = checkpoint.percentiles_outdated;
+        self.runs_since_percentiles = checkpoint.runs_since_percentiles;
+        self.seed = checkpoint.seed;
+        self.computations_per_measurement = checkpoint.computations_per_measurement;
+        self.timer_calibration = checkpoint.timer_calibration;
+        self.warmup_runs_completed = checkpoint.warmup_runs_completed;
+        self.computations_executed = checkpoint.computations_executed;
+        self.discarded_samples = checkpoint.discarded_samples;
+        self.alpha = checkpoint.alpha;
+        self.multiple_testing_correction = checkpoint.multiple_testing_correction;
+        self.first_order_uncropped_enabled = checkpoint.first_order_uncropped_enabled;
+        self.second_order_enabled = checkpoint.second_order_enabled;
+        self.inclusive_crop = checkpoint.inclusive_crop;
+        self.batch_anomaly_rejection = checkpoint.batch_anomaly_rejection;
+        // the elapsed time continues from the checkpoint for the maximum duration
+        self.elapsed_before_resume = checkpoint.elapsed;
+        self.start_time = None;
+        self
+    }
+
+    /// Returns an iterator over the percentile tests with their cropping threshold, t value, and
+    /// number of samples for group a and b, in ascending order of the thresholds.
+    pub fn percentile_iter(&self) -> impl Iterator<Item = (u64, Option<f64>, [f64; 2])> + '_ {
+        // the percentiles are prepared in ascending order
+        self.percentiles
+            .iter()
+            .zip(&self.percentile_tests)
+            .map(|(threshold, test)| (*threshold, test.compute(), test.get_number_of_samples()))
+    }
+
+    /// Returns the number of computations that were executed for all measurement runs, including the warm-up.
+    pub fn computations_executed(&self) -> usize {
+        self.computations_executed
+    }
+
+    /// Returns the wall-clock time since the first measurement run was started.
+    /// It is zero without `std`.
+    pub fn elapsed(&self) -> Duration {
+        let running = self
+            .start_time
+            .zip(self.clock.now())
+            .map(|(start_time, now)| now.saturating_sub(start_time))
+            .unwrap_or_default();
+        self.elapsed_before_resume + running
+    }
+
+    /// Returns the number of executed computations per second of wall-clock time, including the warm-up
+    /// and the time for preparing the inputs and updating the statistics.
+    /// Returns 0 if no measurement run was executed yet.
+    pub fn throughput(&self) -> f64 {
+        let elapsed = self.elapsed().as_secs_f64();
+        if elapsed == 0.0 {
+            0.0
+        } else {
+            self.computations_executed as f64 / elapsed
+        }
+    }
+
+    /// Returns the number of measurements of the test that currently yields the maximum t value.
+    pub fn measurements_collected(&self) -> f64 {
+        let n = self.max_test().1.get_number_of_samples();
+        n[0] + n[1]
+    }
+
+    /// Returns true, if enough measurements were collected for a verdict.
+    pub fn has_enough_measurements(&self) -> bool {
+        self.measurements_collected() >= self.enough_measurements as f64
+    }
+
+    /// Returns the p-values of all tests combined with Fisher's method, or `None` if no test has a p-value.
+    /// The tests are not independent, so the result tends to be too small.
+    pub fn combined_p_value(&self) -> Option<f64> {
+        let p_values: Vec<f64> = self
+            .tests()
+            .iter()
+            .filter_map(|(_, test)| test.p_value())
+            .collect();
+        fisher_combined_p_value(&p_values)
+    }
+
+    /// Returns the confidence interval of the difference of the mean execution times (a - b) in ticks
+    /// at the `confidence` level, or `None` if there are not enough measurements.
+    pub fn mean_difference_interval(&self, confidence: f64) -> Option<(f64, f64)> {
+        let test = if self.first_order_uncropped_enabled {
+            &self.first_order_uncropped_test
+        } else {
+            &self.percentile_tests[self.percentile_tests.len() - 1]
+        };
+        test.mean_difference_confidence_interval(confidence)
+    }
+
+    /// Returns the t value of the cumulative first-order uncropped test.
+    pub fn first_order_uncropped_t(&self) -> Option<f64> {
+        self.first_order_uncropped_test.compute()
+    }
+
+    /// Returns the t value of the windowed first-order uncropped test.
+    /// Returns `None` if the windowed test is not enabled or does not have enough samples.
+    pub fn windowed_first_order_t(&self) -> Option<f64> {
+        self.windowed_first_order_test
+            .as_ref()
+            .and_then(|test| test.compute())
+    }
+
+    /// Executes a measurement run and gives back a result wether or not more runs are required.
+    pub fn execute_measurement_run(&mut self) -> MeasurementRunResult {
+        let result = match self.measurement_run() {
+            MeasurementRunResult::NoLeakageEvidenceYet(report)
+                if self.is_within_equivalence_margin() =>
+            {
+                self.reporter.message(&format!(
+                    "Constant time within +/- {} ticks at {:.0}% confidence.",
+                    self.equivalence_margin.unwrap_or_default(),
+                    EQUIVALENCE_CONFIDENCE_LEVEL * 100.0
+                ));
+                MeasurementRunResult::ConstantTimeWithinMargin(report)
+            }
+            MeasurementRunResult::NoLeakageEvidenceYet(report) if self.stop_condition_reached() => {
+                MeasurementRunResult::NoLeakageEvidence(report)
+            }
+            result => result,
+        };
+        if !result.is_no_leakage_evidence_yet() {
+            self.reporter.verdict_reached(&result);
+        }
+        result
+    }
+
+    /// Returns true, if the maximum number of measurements or the maximum duration is reached.
+    fn stop_condition_reached(&self) -> bool {
+        stop_condition_reached(
+            self.max_measurements,
+            self.max_duration,
+            self.computations_executed,
+            self.elapsed(),
+        )
+    }
+
+    /// Executes a single measurement run without considering the stop conditions.
+    fn measurement_run(&mut self) -> MeasurementRunResult {
+        if self.start_time.is_none() {
+            self.start_time = self.clock.now();
+        }
+        self.reporter.run_started(self.runs_executed);
+        self.environment.get_or_insert_with(environment_fingerprint);
+        if let Some(seed) = self.seed {
+            self.rng = run_rng(seed, self.runs_executed);
+        }
+        let warmup = self.is_warming_up();
+        let first_warmup = warmup && self.warmup_runs_completed == 0;
+        if first_warmup {
+            if self.overhead_calibration_enabled {
+                let calibration = self.calibrate_overhead();
+                self.reporter.message(&format!(
+                    "timer overhead: min {} ticks, median {} ticks.",
+                    calibration.min, calibration.median
+                ));
+                self.timer_calibration = Some(calibration);
+            }
+            self.specimen.warmup();
+        }
+        if warmup || !(self.static_inputs && self.inputs_prepared) {
+            self.prepare_inputs(warmup);
+            // the inputs of the warm-up run are not reused
+            self.inputs_prepared = !warmup;
+        }
+        if first_warmup && self.inputs_appear_identical() {
+            self.reporter
+                .message("group inputs appear identical — check prepare_input_data");
+        }
+        if first_warmup && self.auto_computations_per_measurement {
+            self.computations_per_measurement = self.tune_computations_per_measurement();
+            self.reporter.message(&format!(
+                "{} computations per measurement.",
+                self.computations_per_measurement
+            ));
+        }
+        if let Some(execution_order) = &mut self.execution_order {
+            if self.shuffled_execution {
+                execution_order.shuffle(&mut self.rng);
+            }
+            if self.separate_passes {
+                // the sorting is stable, so the order within the groups is kept
+                let is_group_a = &self.is_group_a;
+                execution_order.sort_by_key(|index| !is_group_a[*index]);
+            }
+        }
+        let measure_start = self.clock.now();
+        self.measure();
+        let measure_duration = measure_start
+            .zip(self.clock.now())
+            .map(|(start, end)| end.saturating_sub(start))
+            .unwrap_or_default();
+        self.runs_executed += 1;
+        self.computations_executed += self.number_of_computations_per_run
+            * self.computations_per_measurement
+            * self.repetitions_per_input;
+        #[cfg(feature = "std")]
+        if let Some(dir) = &self.batch_recorder {
+            let path = dir.join(format!("batch_{}.csv", self.runs_executed));
+            if let Err(error) = self.record_batch(&path) {
+                self.reporter.message(&format!(
+                    "could not record batch to {}: {}",
+                    path.display(),
+                    error
+                ));
+            }
+        }
+
+        let (zero_deltas, measurements) =
+            self.analyzed_times().fold((0, 0), |(zeros, count), time| {
+                (zeros + usize::from(time == 0), count + 1)
+            });
+        let zero_delta_fraction = zero_deltas as f64 / measurements as f64;
+        if zero_delta_fraction > self.max_zero_delta_fraction {
+            self.reporter.message(&format!(
+                "timer resolution insufficient: {:.1} % of the execution times are zero, increase the computations per measurement.",
+                zero_delta_fraction * 100.0
+            ));
+            return MeasurementRunResult::InsufficientTimerResolution(self.current_report());
+        }
+
+        if warmup {
+            // throw away the warm-up batches of measurements.
+            // this helps warming things up.
+            self.prepare_percentiles();
+            self.warmup_runs_completed += 1;
+            if let Some(duration_per_run) =
+                self.auto_batch_duration.filter(|_| !self.is_warming_up())
+            {
+                let duration_per_computation =
+                    measure_duration.as_secs_f64() / self.number_of_computations_per_run as f64;
+                let number_of_computations =
+                    (duration_per_run.as_secs_f64() / duration_per_computation).clamp(
+                        AUTO_BATCH_MIN_COMPUTATIONS as f64,
+                        AUTO_BATCH_MAX_COMPUTATIONS as f64,
+                    ) as usize;
+                self.resize_runs(number_of_computations);
+            }
+            MeasurementRunResult::NoLeakageEvidenceYet(self.current_report())
+        } else {
+            // the run that prepares the percentiles is already part of the streaming percentiles
+            let percentiles_prepared = self.percentiles_outdated;
+            if percentiles_prepared {
+                self.prepare_percentiles();
+                self.percentiles_outdated = false;
+                self.runs_since_percentiles = 0;
+            }
+            let batch_mean = self.mean_analyzed_time();
+            if let Some(rejection) = &mut self.batch_anomaly_rejection {
+                if !rejection.accept(batch_mean) {
+                    self.reporter.message(&format!(
+                        "measurement run discarded: mean execution time {:.2} is anomalous.",
+                        batch_mean
+                    ));
+                    return MeasurementRunResult::NoLeakageEvidenceYet(self.current_report());
+                }
+            }
+            self.update_statistics();
+            #[cfg(feature = "std")]
+            self.export_csv();
+            if !percentiles_prepared {
+                self.update_streaming_percentiles();
+            }
+            let result = self.report();
+            self.runs_since_percentiles += 1;
+            if let Some(interval) = self.percentile_reestimation_interval {
+                if self.runs_since_percentiles >= interval {
+                    self.recompute_percentiles();
+                }
+            }
+            result
+        }
+    }
+
+    /// Returns true, if no byte of the inputs differs significantly between the groups of the current run.
+    /// Returns false, if the inputs are not plain byte sequences (see `MeasurementInput::bytes`).
+    fn inputs_appear_identical(&self) -> bool {
+        let bytes: Option<Vec<&[u8]>> = (0..self.number_of_computations_per_run)
+            .map(|index| self.input(index).bytes())
+            .collect();
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+        let length = bytes.iter().map(|bytes| bytes.len()).min().unwrap_or(0);
+        if length == 0 {
+            // there are no bytes that could be compared
+            return false;
+        }
+        (0..length).all(|byte| {
+            let mut t_test = TTest::new();
+            for (index, input) in bytes.iter().enumerate() {
+                t_test.push(input[byte] as f64, self.is_group_a[index]);
+            }
+            match t_test.compute() {
+                Some(t) => f64::abs(t) < IDENTICAL_INPUTS_MAX_T,
+                // both groups are constant
+                None => {
+                    let mean = t_test.get_mean();
+                    mean[0] == mean[1]
+                }
+            }
+        })
+    }
+
+    /// Measures the overhead of the measurement loop with the inputs, but without executing the computations.
+    fn calibrate_overhead(&mut self) -> TimerCalibration {
+        let mut overheads = Vec::with_capacity(CALIBRATION_SAMPLES);
+        let mut previous_tick = self.cycle_counter.ticks();
+        for i in 0..CALIBRATION_SAMPLES {
+            let index = i % self.number_of_computations_per_run;
+            black_box(input_of(&self.input_pool, &self.input_data, index));
+            let tick = self.cycle_counter.ticks();
+            overheads.extend(TickOverflowPolicy::Discard.execution_time(previous_tick, tick));
+            previous_tick = tick;
+        }
+        overheads.sort_unstable();
+        TimerCalibration {
+            min: overheads.first().copied().unwrap_or(0),
+            median: overheads.get(overheads.len() / 2).copied().unwrap_or(0),
+        }
+    }
+
+    /// Returns the smallest change of the ticks of the cycle counter, or `None` if the counter does not change.
+    fn timer_granularity(&mut self) -> Option<u64> {
+        let mut granularity = None;
+        for _ in 0..GRANULARITY_SAMPLES {
+            let start = self.cycle_counter.ticks();
+            let change = (0..GRANULARITY_MAX_READS)
+                .map(|_| self.cycle_counter.ticks())
+                .find(|tick| *tick != start)
+                .map(|tick| tick.wrapping_sub(start));
+            granularity = match (granularity, change) {
+                (Some(granularity), Some(change)) => Some(u64::min(granularity, change)),
+                (None, change) => change,
+                (granularity, None) => granularity,
+            };
+        }
+        granularity
+    }
+
+    /// Returns the number of computations per measurement whose execution time is large enough compared to
+    /// the timer granularity, see `with_auto_computations_per_measurement`.
+    fn tune_computations_per_measurement(&mut self) -> usize {
+        let granularity = match self.timer_granularity() {
+            Some(granularity) => granularity,
+            None => return self.computations_per_measurement,
+        };
+        let input = input_of(&self.input_pool, &self.input_data, 0);
+        let mut computations = 1;
+        loop {
+            let start = self.cycle_counter.ticks();
+            for _ in 0..computations {
+                black_box(self.specimen.do_one_computation(black_box(input)));
+            }
+            let end = self.cycle_counter.ticks();
+            let execution_time = TickOverflowPolicy::Discard
+                .execution_time(start, end)
+                .unwrap_or(0);
+            if execution_time >= AUTO_MEASUREMENT_MIN_GRANULARITIES.saturating_mul(granularity)
+                || computations >= AUTO_MEASUREMENT_MAX_COMPUTATIONS
+            {
+                return computations;
+            }
+            computations *= 2;
+        }
+    }
+
+    /// Writes the analyzed execution times of the current measurement run to the CSV export, if it is enabled.
+    #[cfg(feature = "std")]
+    fn export_csv(&mut self) {
+        // the writer is taken, so that it can be used while reading the other fields
+        if let Some(mut writer) = self.csv_export.take() {
+            match crate::report::write_csv_rows(&mut writer, self.runs_executed, self.csv_rows()) {
+                Ok(()) => self.csv_export = Some(writer),
+                Err(error) => self
+                    .reporter
+                    .message(&format!("CSV export stopped: {}", error)),
+            }
+        }
+    }
+
+    /// Writes the analyzed execution times of the current measurement run to the file at `path`.
+    #[cfg(feature = "std")]
+    fn record_batch(&self, path: &Path) -> io::Result<()> {
+        crate::report::write_csv_file(path, self.runs_executed, self.csv_rows())
+    }
+
+    /// Returns the rows of the CSV export: the execution time and the group of each analyzed position
+    /// of the current measurement run.
+    #[cfg(feature = "std")]
+    fn csv_rows(&self) -> impl Iterator<Item = (u64, bool)> + '_ {
+        self.analyzed_positions().map(|i| {
+            (
+                self.execution_times[i],
+                self.is_group_a[self.input_index(i)],
+            )
+        })
+    }
+
+    /// Panics if the discarded samples leave no execution time of a run with `number_of_computations_per_run`
+    /// computations for the statistics.
+    fn assert_analyzed_samples(&self, number_of_computations_per_run: usize) {
+        assert!(
+            self.discarded_leading_samples + self.discarded_trailing_samples
+                < number_of_computations_per_run,
+            "{} leading and {} trailing discarded samples leave no samples of a run of {} computations",
+            self.discarded_leading_samples,
+            self.discarded_trailing_samples,
+            number_of_computations_per_run
+        );
+    }
+
+    /// Changes the number of computations per run and resizes all buffers accordingly.
+    ///
+    /// Panics if the discarded samples leave no execution time of a run for the statistics.
+    fn resize_runs(&mut self, number_of_computations_per_run: usize) {
+        self.assert_analyzed_samples(number_of_computations_per_run);
+        self.number_of_computations_per_run = number_of_computations_per_run;
+        self.ticks.resize(number_of_computations_per_run, 0);
+        self.execution_times
+            .resize(number_of_computations_per_run, 0);
+        self.dropped.resize(number_of_computations_per_run, false);
+        self.is_group_a
+            .resize(number_of_computations_per_run, false);
+        match &mut self.input_pool {
+            Some(pool) => pool.indices.resize(number_of_computations_per_run, 0),
+            None => self
+                .input_data
+                .resize(number_of_computations_per_run, self.initial_input.clone()),
+        }
+        if let Some(execution_order) = &mut self.execution_order {
+            *execution_order = (0..number_of_computations_per_run).collect();
+        }
+        if let Some(wall_clock) = &mut self.wall_clock {
+            let first_instant = wall_clock.first_instant;
+            wall_clock
+                .instants
+                .resize(number_of_computations_per_run, first_instant);
+            wall_clock
+                .execution_times
+                .resize(number_of_computations_per_run, 0);
+        }
+        if let Some(segmented) = &mut self.segmented {
+            segmented
+                .marks
+                .resize(number_of_computations_per_run, Vec::new());
+        }
+        // the inputs must be prepared for the new size
+        self.inputs_prepared = false;
+    }
+
+    /// Randomizes the group assignment and prepares the input data for the next measurement run.
+    /// If `warmup` is true, the input data for the warm-up run is prepared instead.
+    fn prepare_inputs(&mut self, warmup: bool) {
+        if let Some(corpus) = &mut self.corpus {
+            for (input, is_group_a) in self.input_data.iter_mut().zip(&mut self.is_group_a) {
+                *input = corpus.inputs[corpus.next_index].clone();
+                *is_group_a = corpus.is_group_a[corpus.next_index];
+                corpus.next_index = (corpus.next_index + 1) % corpus.inputs.len();
+            }
+        } else {
+            // randomize is_group_a
+            for i in &mut self.is_group_a {
+                *i = self.rng.gen::<bool>();
+            }
+
+            match &mut self.input_pool {
+                Some(pool) => {
+                    for (index, is_group_a) in pool.indices.iter_mut().zip(&self.is_group_a) {
+                        let group_indices = &pool.group_indices[usize::from(!*is_group_a)];
+                        *index = group_indices[self.rng.gen_range(0..group_indices.len())];
+                    }
+                }
+                None if warmup => self
+                    .specimen
+                    .prepare_warmup_data(&mut self.input_data, &self.is_group_a),
+                None => self
+                    .specimen
+                    .prepare_input_data(&mut self.input_data, &self.is_group_a),
+            }
+        }
+        if let Some(input_grouping) = self.input_grouping {
+            for i in 0..self.number_of_computations_per_run {
+                self.is_group_a[i] = input_grouping(self.input(i));
+            }
+        }
+    }
+
+    fn measure(&mut self) {
+        let clock = self.clock;
+        if let Some(wall_clock) = &mut self.wall_clock {
+            wall_clock.first_instant = clock.now().unwrap_or_default();
+        }
+        if let Some(outputs) = &mut self.outputs {
+            outputs.clear();
+        }
+        let overhead = self
+            .timer_calibration
+            .map_or(0, |calibration| calibration.min);
+        // the execution times of repeated measurements are computed directly, not from the ticks between the inputs
+        let repeated = self.repetitions_per_input > 1;
+        self.first_tick = self.cycle_counter.ticks();
+        for i in 0..self.number_of_computations_per_run {
+            let input = input_of(&self.input_pool, &self.input_data, self.input_index(i));
+            let output = match &mut self.segmented {
+                Some(segmented) => {
+                    let marks = &mut segmented.marks[i];
+                    marks.clear();
+                    let mut recorder = MarkRecorder {
+                        cycle_counter: &mut *self.cycle_counter,
+                        marks,
+                    };
+                    self.specimen
+                        .do_one_computation_segmented(black_box(input), &mut recorder)
+                }
+                None if repeated => {
+                    let mut min_execution_time = None;
+                    let mut output = None;
+                    for _ in 0..self.repetitions_per_input {
+                        let start_tick = self.cycle_counter.ticks();
+                        for _ in 1..self.computations_per_measurement {
+                            black_box(self.specimen.do_one_computation(black_box(input)));
+                        }
+                        output = Some(black_box(
+                            self.specimen.do_one_computation(black_box(input)),
+                        ));
+                        let end_tick = self.cycle_counter.ticks();
+                        if end_tick < start_tick {
+                            self.tick_overflows += 1;
+                        }
+                        let execution_time = self
+                            .tick_overflow_policy
+                            .execution_time(start_tick, end_tick);
+                        min_execution_time = match (min_execution_time, execution_time) {
+                            (Some(min), Some(time)) => Some(u64::min(min, time)),
+                            (min, time) => min.or(time),
+                        };
+                    }
+                    self.dropped[i] = min_execution_time.is_none();
+                    self.execution_times[i] =
+                        min_execution_time.unwrap_or(0).saturating_sub(overhead);
+                    output.expect("at least one repetition is executed")
+                }
+                None => {
+                    for _ in 1..self.computations_per_measurement {
+                        black_box(self.specimen.do_one_computation(black_box(input)));
+                    }
+                    self.specimen.do_one_computation(black_box(input))
+                }
+            };
+            let output = black_box(output);
+            self.ticks[i] = self.cycle_counter.ticks();
+            if let Some(outputs) = &mut self.outputs {
+                outputs.push(output);
+            }
+            if let Some(wall_clock) = &mut self.wall_clock {
+                wall_clock.instants[i] = clock.now().unwrap_or_default();
+            }
+        }
+        if let Some(wall_clock) = &mut self.wall_clock {
+            for i in 0..wall_clock.instants.len() {
+                let previous_instant = if i == 0 {
+                    wall_clock.first_instant
+                } else {
+                    wall_clock.instants[i - 1]
+                };
+                wall_clock.execution_times[i] = wall_clock.instants[i]
+                    .saturating_sub(previous_instant)
+                    .as_nanos() as u64;
+            }
+        }
+        for i in 0..self.ticks.len() {
+            if !repeated {
+                let previous_tick = if i == 0 {
+                    self.first_tick
+                } else {
+                    self.ticks[i - 1]
+                };
+                let current_tick = self.ticks[i];
+                if current_tick < previous_tick {
+                    self.tick_overflows += 1;
+                }
+                let execution_time = self
+                    .tick_overflow_policy
+                    .execution_time(previous_tick, current_tick);
+                self.dropped[i] = execution_time.is_none();
+                self.execution_times[i] = execution_time.unwrap_or(0).saturating_sub(overhead);
+            }
+            let input_index = self.input_index(i);
+            let input = input_of(&self.input_pool, &self.input_data, input_index);
+            if !self.dropped[i] {
+                self.specimen
+                    .on_measurement(input_index, input, self.execution_times[i]);
+            }
+            if let Some(outputs) = &self.outputs {
+                self.specimen.verify_output(input, &outputs[i]);
+            }
+        }
+    }
+
+    /// Returns the index of the input that was executed at the given position of the current measurement run.
+    #[inline(always)]
+    fn input_index(&self, position: usize) -> usize {
+        match &self.execution_order {
+            Some(execution_order) => execution_order[position],
+            None => position,
+        }
+    }
+
+    /// Returns the input for the computation with the given index of the current measurement run.
+    #[inline(always)]
+    fn input(&self, index: usize) -> &T::Input {
+        input_of(&self.input_pool, &self.input_data, index)
+    }
+
+    /// Prepare the percentiles with the values of the execution times as a baseline.
+    /// From dudect:
+    /// set different thresholds for cropping measurements.
+    /// the exponential tendency is meant to approximately match
+    /// the measurements distribution, but there's not more science
+    /// than that.
+    /// The streaming percentiles start over with the execution times instead.
+    fn prepare_percentiles(&mut self) {
+        let measured_times = (0..self.number_of_computations_per_run)
+            .filter(|i| !self.dropped[*i])
+            .map(|i| self.execution_times[i]);
+        if let Some(sketch) = &mut self.streaming_percentiles {
+            *sketch = QuantileSketch::new();
+            measured_times.for_each(|time| sketch.push(time));
+            self.update_streaming_thresholds();
+            return;
+        }
+        // sort a copy, as the order of the execution times is still required for the statistics
+        let mut sorted_execution_times: Vec<u64> = measured_times.collect();
+        // it is not important for the sorting to keep the order of equal elements
+        sorted_execution_times.sort_unstable();
+        for i in 0..self.percentiles.len() {
+            self.percentiles[i] =
+                percentile_sorted(&sorted_execution_times, self.percentile_fraction(i))
+                    .expect("there must be execution times for calculating the percentiles");
+        }
+    }
+
+    /// Returns the fraction of the percentile that is the cropping threshold of the percentile test `index`.
+    fn percentile_fraction(&self, index: usize) -> f64 {
+        1.0 - f64::powf(
+            0.5,
+            self.percentile_curve_exponent * (index as f64 + 1.0) / self.percentiles.len() as f64,
+        )
+    }
+
+    /// Adds the analyzed execution times of the current measurement run to the streaming percentiles
+    /// and updates the cropping thresholds with their estimates.
+    fn update_streaming_percentiles(&mut self) {
+        // the sketch is taken, so that it can be updated while reading the execution times
+        if let Some(mut sketch) = self.streaming_percentiles.take() {
+            self.analyzed_times().for_each(|time| sketch.push(time));
+            self.streaming_percentiles = Some(sketch);
+            self.update_streaming_thresholds();
+        }
+    }
+
+    /// Sets the cropping thresholds to the estimates of the streaming percentiles, if they are enabled.
+    fn update_streaming_thresholds(&mut self) {
+        let fractions: Vec<f64> = (0..self.percentiles.len())
+            .map(|index| self.percentile_fraction(index))
+            .collect();
+        if let Some(sketch) = &self.streaming_percentiles {
+            for (threshold, estimate) in self
+                .percentiles
+                .iter_mut()
+                .zip(sketch.quantiles(&fractions))
+            {
+                if let Some(estimate) = estimate {
+                    *threshold = estimate;
+                }
+            }
+        }
+    }
+
+    /// Returns the indices of the execution times of a measurement run that are used for the statistics.
+    pub(crate) fn analyzed_indices(&self) -> core::ops::Range<usize> {
+        let end = self
+            .number_of_computations_per_run
+            .saturating_sub(self.discarded_trailing_samples);
+        usize::min(self.discarded_leading_samples, end)..end
+    }
+
+    /// Returns the analyzed indices whose execution time was not dropped.
+    fn analyzed_positions(&self) -> impl Iterator<Item = usize> + '_ {
+        self.analyzed_indices().filter(move |i| !self.dropped[*i])
+    }
+
+    /// Returns the execution times of the current measurement run that are used for the statistics.
+    fn analyzed_times(&self) -> impl Iterator<Item = u64> + '_ {
+        self.analyzed_positions()
+            .map(move |i| self.execution_times[i])
+    }
+
+    /// Returns the mean of the execution times of the current measurement run that are used for the statistics.
+    fn mean_analyzed_time(&self) -> f64 {
+        let (sum, count) = self
+            .analyzed_times()
+            .fold((0.0, 0.0), |(sum, count), time| {
+                (sum + time as f64, count + 1.0)
+            });
+        sum / count
+    }
+
+    fn update_statistics(&mut self) {
+        let overhead = self
+            .timer_calibration
+            .map_or(0, |calibration| calibration.min);
+        // discard the first few and the last measurements, and the dropped execution times
+        let analyzed_positions: Vec<usize> = self.analyzed_positions().collect();
+        self.discarded_samples += self.number_of_computations_per_run - analyzed_positions.len();
+        for i in analyzed_positions {
+            let execution_time = self.execution_times[i];
+            let difference = execution_time as f64;
+            let is_group_a = self.is_group_a[self.input_index(i)];
+
+            // t-test and the other first-order tests on the execution time
+            for test in self.first_order_tests_mut() {
+                test.push(difference, is_group_a);
+            }
+            if let Some(reservoir) = &mut self.reservoir {
+                reservoir.push(execution_time, is_group_a);
+            }
+            if let Some(histogram) = &mut self.histogram {
+                histogram.push(execution_time, is_group_a);
+            }
+            if let Some(segmented) = &mut self.segmented {
+                let start_tick = if i == 0 {
+                    self.first_tick
+                } else {
+                    self.ticks[i - 1]
+                };
+                let marks = &segmented.marks[i];
+                let boundaries = core::iter::once(start_tick)
+                    .chain(marks.iter().copied())
+                    .chain(core::iter::once(self.ticks[i]));
+                let phase_times = boundaries.clone().zip(boundaries.skip(1));
+                if segmented.tests.len() < marks.len() + 1 {
+                    segmented.tests.resize(marks.len() + 1, TTest::new());
+                }
+                for (test, (start, end)) in segmented.tests.iter_mut().zip(phase_times) {
+                    // each phase ends with a read of the cycle counter, like a whole computation
+                    if let Some(phase_time) = self.tick_overflow_policy.execution_time(start, end) {
+                        test.push(phase_time.saturating_sub(overhead) as f64, is_group_a);
+                    }
+                }
+            }
+            if let Some(wall_clock) = &mut self.wall_clock {
+                wall_clock
+                    .first_order_uncropped_test
+                    .push(wall_clock.execution_times[i] as f64, is_group_a);
+            }
+
+            // t-test on cropped execution times, for several cropping thresholds
+            for crop_index in 0..self.percentiles.len() {
+                // compare the integers to not depend on the precision of the conversion to f64
+                let threshold = self.percentiles[crop_index];
+                let included = if self.inclusive_crop {
+                    execution_time <= threshold
+                } else {
+                    execution_time < threshold
+                };
+                if included {
+                    self.percentile_tests[crop_index].push(difference, is_group_a);
+                }
+            }
+
+            // second-order and higher-order tests (only if we have enough measurements).
+            // Centered product pre-processing.
+            // without the uncropped test, the test with the largest threshold contains nearly all samples
+            let first_order_samples = if self.first_order_uncropped_enabled {
+                self.first_order_uncropped_test.get_number_of_samples()
+            } else {
+                self.percentile_tests[self.percentile_tests.len() - 1].get_number_of_samples()
+            };
+            if (self.second_order_enabled || !self.higher_order_tests.is_empty())
+                && first_order_samples[0] + first_order_samples[1] > self.second_order_min_samples
+                && self.percentile_tests[0]
+                    .get_number_of_samples()
+                    .iter()
+                    .all(|n| *n > 0.0)
+            {
+                let group_index = if is_group_a { 0 } else { 1 };
+                let centered = difference - self.percentile_tests[0].get_mean()[group_index];
+                if self.second_order_enabled {
+                    self.second_order_test.push(centered * centered, is_group_a);
+                }
+
+                // standardize the samples, so that the powers of high orders stay in a reasonable range
+                let n = self.percentile_tests[0].get_number_of_samples()[group_index];
+                let m2 = self.percentile_tests[0].get_m2()[group_index];
+                if !self.higher_order_tests.is_empty() && n > 1.0 && m2 > 0.0 {
+                    let standardized = centered / f64::sqrt(m2 / (n - 1.0));
+                    let mut power = standardized * standardized;
+                    for test in &mut self.higher_order_tests {
+                        power *= standardized;
+                        test.push(power, is_group_a);
+                    }
+                }
+            }
+        }
+    }
+
+    fn report(&mut self) -> MeasurementRunResult {
+        let report = self.current_report();
+        let resolution = self.timing_resolution();
+        let mean_time = self.mean_analyzed_time();
+        if resolution > 0 && mean_time < RESOLUTION_LIMITED_FACTOR * resolution as f64 {
+            self.reporter.message(&format!(
+                "mean execution time {:.2} is close to the timing resolution {}: results may be resolution-limited.",
+                mean_time, resolution
+            ));
+        }
+        let (leakage_found, summary) = self.format_verdict(&report);
+        if let Some(history) = &mut self.history {
+            history.push((report.number_of_measurements, report.max_t, report.max_tau));
+        }
+        self.last_report = Some(report.clone());
+        let result = MeasurementRunResult::graded(leakage_found, report);
+        self.reporter.run_finished(&result, &summary);
+        result
+    }
+
+    /// Returns the statistics of the test that currently yields the maximum t value.
+    fn current_report(&self) -> MeasurementReport {
+        let (max_test, test) = self.max_test();
+        let max_t = test.statistic().unwrap_or(0.0);
+        let number_traces_max_t = {
+            let n = test.get_number_of_samples();
+            n[0] + n[1]
+        };
+        let degrees_of_freedom = test.degrees_of_freedom();
+        let p_value = test.p_value();
+        let max_tau = tau(max_t, number_traces_max_t);
+        let wall_clock_t = self.wall_clock_t().map(f64::abs);
+        let wall_clock_leakage_found = wall_clock_t.map(|t| t > self.t_threshold_moderate);
+
+        MeasurementReport {
+            max_test,
+            number_of_measurements: number_traces_max_t,
+            max_t,
+            max_tau,
+            t_threshold_overwhelming: self.t_threshold_overwhelming,
+            degrees_of_freedom,
+            p_value,
+            corrected_p_value: self.alpha.map(|_| self.corrected_p_value()),
+            custom_statistics: self.custom_statistics(),
+            mean_difference_interval: self.mean_difference_interval(CONFIDENCE_LEVEL),
+            throughput: self.throughput(),
+            cycle_counter: self.cycle_counter.name(),
+            wall_clock_t,
+            wall_clock_leakage_found,
+            wall_clock_disagreement: wall_clock_leakage_found
+                .map(|leakage_found| leakage_found != (max_t > self.t_threshold_moderate)),
+            overlap_coefficient: self.reservoir.as_ref().map(|reservoir| {
+                let (samples_a, samples_b) = reservoir.get_samples();
+                overlap_coefficient(samples_a, samples_b, OVERLAP_BINS)
+            }),
+            kolmogorov_smirnov: self.kolmogorov_smirnov(),
+            anderson_darling: self.anderson_darling(),
+            mutual_information: self.mutual_information(),
+            timer_calibration: self.timer_calibration,
+            computations_per_measurement: self.computations_per_measurement,
+            discarded_samples: self.discarded_samples,
+            environment: self
+                .environment
+                .clone()
+                .unwrap_or_else(environment_fingerprint),
+        }
+    }
+
+    /// Formats the statistics of the test that yielded max t and the verdict as a single line
+    /// and returns true, if leakage was found.
+    fn format_verdict(&self, report: &MeasurementReport) -> (bool, String) {
+        let number_traces_max_t = report.number_of_measurements;
+        let max_t = report.max_t;
+        let max_tau = report.max_tau;
+        // report the number of measurements of the test that yielded max t.
+        // sometimes you can see this number go down - this can be confusing
+        // but can happen (different test)
+        let (enough_measurements, mut summary) =
+            format_measurements(number_traces_max_t, self.enough_measurements);
+        if !enough_measurements {
+            return (false, summary);
+        }
+
+        /*
+         * We report the following statistics:
+         *
+         * max_t: the t value
+         * max_tau: a t value normalized by sqrt(number of measurements).
+         *          this way we can compare max_tau taken with different
+         *          number of measurements. This is sort of "distance
+         *          between distributions", independent of number of
+         *          measurements.
+         * (5/tau)^2: how many measurements we would need to barely
+         *            detect the leak, if present. "barely detect the
+         *            leak" here means have a t value greater than 5.
+         *
+         * The first metric is standard; the other two aren't (but
+         * pretty sensible imho)
+         */
+
+        summary += &format!(
+            "max t: {:>7.2} ({}), max tau: {:.2e}, (5/tau)^2: {}.",
+            max_t,
+            report.max_test,
+            max_tau,
+            format_required_measurements(max_tau)
+        );
+        if let (Some(degrees_of_freedom), Some(p_value)) =
+            (report.degrees_of_freedom, report.p_value)
+        {
+            summary += &format!(" df: {:.0}, p: {:.2e}.", degrees_of_freedom, p_value);
+        }
+        if let Some((lower, upper)) = report.mean_difference_interval {
+            summary += &format!(
+                " mean difference: [{:.2}, {:.2}] ticks ({:.0}% CI).",
+                lower,
+                upper,
+                CONFIDENCE_LEVEL * 100.0
+            );
+        }
+        if let Some((statistic, p_value)) = report.kolmogorov_smirnov {
+            summary += &format!(" KS D: {:.4} (p: {:.2e}).", statistic, p_value);
+        }
+        if let Some((statistic, p_value)) = report.anderson_darling {
+            summary += &format!(" AD T: {:.2} (p: {:.2e}).", statistic, p_value);
+        }
+        if let Some(mutual_information) = report.mutual_information {
+            summary += &format!(" MI: {:.2e} bits.", mutual_information);
+        }
+        if self.windowed_first_order_test.is_some() {
+            summary += &format!(
+                " window t: {:>7.2}, cumulative t: {:>7.2}.",
+                self.windowed_first_order_t().unwrap_or(0.0),
+                self.first_order_uncropped_t().unwrap_or(0.0)
+            );
+        }
+        if self.segmented.is_some() {
+            let segment_t_values: Vec<String> = self
+                .segment_t_values()
+                .iter()
+                .map(|t| format!("{:.2}", f64::abs(t.unwrap_or(0.0))))
+                .collect();
+            summary += &format!(" phase t: [{}].", segment_t_values.join(", "));
+        }
+        if !self.higher_order_tests.is_empty() {
+            let higher_order_t_values: Vec<String> = self
+                .higher_order_t_values()
+                .iter()
+                .map(|t| format!("{:.2}", f64::abs(t.unwrap_or(0.0))))
+                .collect();
+            summary += &format!(" higher-order t: [{}].", higher_order_t_values.join(", "));
+        }
+        if self.wall_clock.is_some() {
+            let wall_clock_verdict = if report.wall_clock_leakage_found == Some(true) {
+                "not constant time"
+            } else {
+                "maybe constant time"
+            };
+            summary += &format!(
+                " wall-clock t: {:>7.2} ({}).",
+                report.wall_clock_t.unwrap_or(0.0),
+                wall_clock_verdict
+            );
+            if report.wall_clock_disagreement == Some(true) {
+                summary +=
+                    " The cycle counter and the wall clock disagree, check the cycle counter.";
+            }
+        }
+        let mut custom_verdict = None;
+        for test in &self.custom_tests {
+            let statistic = test.statistic().unwrap_or(0.0);
+            summary += &format!(" {}: {:.2}.", test.name(), statistic);
+            if custom_verdict.is_none() && statistic > test.threshold() {
+                custom_verdict = Some(format!(
+                    " Not constant time according to the {} test (threshold {:.2}).",
+                    test.name(),
+                    test.threshold()
+                ));
+            }
+        }
+        if let Some(alpha) = self.alpha {
+            let corrected_p_value = self.corrected_p_value();
+            summary += &format!(" corrected p: {:.2e}.", corrected_p_value);
+            if corrected_p_value < alpha {
+                summary += &format!(" Not constant time (alpha = {:.0e}).", alpha);
+                return (true, summary);
+            }
+        }
+        if let Some(custom_verdict) = custom_verdict {
+            summary += &custom_verdict;
+            return (true, summary);
+        }
+        if let Some(alpha) = self.alpha {
+            summary += &format!(
+                " For the moment, maybe constant time (alpha = {:.0e}).",
+                alpha
+            );
+            return (false, summary);
+        }
+        // the custom tests were graded against their own thresholds above
+        let max_t = match report.max_test {
+            TestKind::Custom(_) => self
+                .tests()
+                .iter()
+                .filter(|(kind, _)| !matches!(kind, TestKind::Custom(_)))
+                .filter_map(|(_, test)| test.statistic())
+                .fold(0.0, f64::max),
+            _ => max_t,
+        };
+        let (leakage_found, verdict) = grade_t(
+            max_t,
+            self.t_threshold_moderate,
+            self.t_threshold_overwhelming,
+        );
+        summary += " ";
+        summary += verdict;
+        (leakage_found, summary)
+    }
+
+    /// Returns all tests that are considered for the verdict with their identity.
+    /// The first-order uncropped and the second-order test are only included if they are enabled.
+    fn tests(&self) -> Vec<(TestKind, &dyn StatisticalTest)> {
+        let mut tests: Vec<(TestKind, &dyn StatisticalTest)> = Vec::with_capacity(
+            self.percentile_tests.len()
+                + self.higher_order_tests.len()
+                + self.custom_tests.len()
+                + 4,
+        );
+        for (index, test) in self.percentile_tests.iter().enumerate() {
+            let kind = TestKind::Percentile {
+                index,
+                threshold: self.percentiles.get(index).copied().unwrap_or(0),
+            };
+            tests.push((kind, test));
+        }
+        if self.first_order_uncropped_enabled {
+            tests.push((
+                TestKind::FirstOrderUncropped,
+                &self.first_order_uncropped_test,
+            ));
+        }
+        if self.second_order_enabled {
+            tests.push((TestKind::SecondOrder, &self.second_order_test));
+        }
+        for (index, test) in self.higher_order_tests.iter().enumerate() {
+            tests.push((TestKind::HigherOrder(index + 3), test));
+        }
+        if let Some(robust_test) = &self.robust_test {
+            tests.push((TestKind::Robust, robust_test));
+        }
+        if let Some(mann_whitney_test) = &self.mann_whitney_test {
+            tests.push((TestKind::MannWhitney, mann_whitney_test));
+        }
+        for (index, test) in self.custom_tests.iter().enumerate() {
+            tests.push((TestKind::Custom(index), test.as_ref()));
+        }
+        tests
+    }
+
+    /// Returns the tests that are fed with every analyzed execution time: the first-order uncropped test
+    /// (if it is enabled), the windowed, the robust, the Mann-Whitney U, and the custom tests.
+    fn first_order_tests_mut(&mut self) -> impl Iterator<Item = &mut dyn StatisticalTest> + '_ {
+        let first_order_uncropped_test = if self.first_order_uncropped_enabled {
+            Some(&mut self.first_order_uncropped_test as &mut dyn StatisticalTest)
+        } else {
+            None
+        };
+        first_order_uncropped_test
+            .into_iter()
+            .chain(
+                self.windowed_first_order_test
+                    .as_mut()
+                    .map(|test| test as &mut dyn StatisticalTest),
+            )
+            .chain(
+                self.robust_test
+                    .as_mut()
+                    .map(|test| test as &mut dyn StatisticalTest),
+            )
+            .chain(
+                self.mann_whitney_test
+                    .as_mut()
+                    .map(|test| test as &mut dyn StatisticalTest),
+            )
+            .chain(
+                self.custom_tests
+                    .iter_mut()
+                    .map(|test| test.as_mut() as &mut dyn StatisticalTest),
+            )
+    }
+
+    /// Returns the smallest p-value of all tests corrected for the number of tests.
+    /// Tests without a p-value are ignored, but are still counted for the correction.
+    fn corrected_p_value(&self) -> f64 {
+        let tests = self.tests();
+        let min_p_value = tests
+            .iter()
+            .filter_map(|(_, test)| test.p_value())
+            .fold(1.0, f64::min);
+        self.multiple_testing_correction
+            .correct(min_p_value, tests.len())
+    }
+
+    /// Find the test with the maximum statistic of all tests (see `tests`) and return it with its identity.
+    /// On ties, the first of the tests wins.
+    fn max_test(&self) -> (TestKind, &dyn StatisticalTest) {
+        // compare the absolute statistics, as a large negative t value (group b slower) is as relevant as a positive one.
+        // Tests without a statistic and NaN statistics are smaller than any other statistic.
+        fn max_test_function(a: &dyn StatisticalTest, b: &dyn StatisticalTest) -> Ordering {
+            let a_value = a.statistic().filter(|t| !t.is_nan());
+            let b_value = b.statistic().filter(|t| !t.is_nan());
+            a_value.partial_cmp(&b_value).unwrap_or(Ordering::Equal)
+        }
+
+        self.tests()
+            .into_iter()
+            .reduce(|max, test| {
+                if max_test_function(max.1, test.1) == Ordering::Less {
+                    test
+                } else {
+                    max
+                }
+            })
+            .unwrap()
+    }
+}
+
+impl<T: MeasurementSpecimen<Input = Vec<u8>>> MeasurementContext<T> {
+    /// Create a new measurement context for byte inputs with a length of `input_length`, which is chosen
+    /// at runtime (e.g. from a configuration file) instead of with a const generic array.
+    pub fn from_input_length(specimen: T, config: DudectConfig, input_length: usize) -> Self {
+        Self::from_initial_input(specimen, config, vec![0u8; input_length])
+    }
+}
+
+impl<T: PairedMeasurementSpecimen> PairedMeasurementContext<T> {
+    /// Create a new paired context, which measures `config.number_of_computations_per_run` pairs
+    /// per measurement run.
+    pub fn new(specimen: T, config: DudectConfig) -> Self {
+        let number_of_pairs_per_run = config.number_of_computations_per_run;
+        Self {
+            specimen,
+            number_of_pairs_per_run,
+            enough_measurements: config.enough_measurements,
+            t_threshold_moderate: config.t_threshold_moderate,
+            t_threshold_overwhelming: config.t_threshold_overwhelming,
+            max_measurements: config.max_measurements,
+            max_duration: config.max_duration,
+            inputs: vec![(T::Input::initial(), T::Input::initial()); number_of_pairs_per_run],
+            a_first: vec![false; number_of_pairs_per_run],
+            execution_times: vec![None; number_of_pairs_per_run],
+            test: PairedTTest::new(),
+            cycle_counter: default_cycle_counter(),
+            rng: default_rng(),
+            seed: None,
+            runs_executed: 0,
+            clock: MonotonicClock::new(),
+            start_time: None,
+            environment: None,
+            reporter: default_reporter(),
+        }
+    }
+
+    /// Use `cycle_counter` for the timestamps of the measurements instead of the default counter of the architecture.
+    #[must_use]
+    pub fn with_cycle_counter<C: CycleCounter + 'static>(mut self, cycle_counter: C) -> Self {
+        self.cycle_counter = Box::new(cycle_counter);
+        self
+    }
+
+    /// Send the progress and the results of the measurement runs to `reporter` instead of printing them to stdout
+    /// (see [`ConsoleReporter`]), e.g. [`SilentReporter`] inside a test harness or a custom reporter for a GUI.
+    #[must_use]
+    pub fn with_reporter<R: Reporter + 'static>(mut self, reporter: R) -> Self {
+        self.reporter = Box::new(reporter);
+        self
+    }
+
+    /// Derive the inputs and the order within each pair from `seed` and the number of the measurement run,
+    /// so a campaign can be repeated.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the t value of the paired test.
+    pub fn paired_t(&self) -> Option<f64> {
+        self.test.compute()
+    }
+
+    /// Returns the wall-clock time since the first measurement run was started.
+    /// It is zero without `std`.
+    pub fn elapsed(&self) -> Duration {
+        self.start_time
+            .zip(self.clock.now())
+            .map(|(start_time, now)| now.saturating_sub(start_time))
+            .unwrap_or_default()
+    }
+
+    /// Execute a single measurement run of all pairs in a random order within each pair.
+    /// The first measurement run is a warm-up run.
+    pub fn execute_measurement_run(&mut self) -> MeasurementRunResult {
+        let result = match self.measurement_run() {
+            MeasurementRunResult::NoLeakageEvidenceYet(report) if self.stop_condition_reached() => {
+                MeasurementRunResult::NoLeakageEvidence(report)
+            }
+            result => result,
+        };
+        if !result.is_no_leakage_evidence_yet() {
+            self.reporter.verdict_reached(&result);
+        }
+        result
+    }
+
+    /// Returns true, if the maximum number of measurements or the maximum duration is reached.
+    fn stop_condition_reached(&self) -> bool {
+        stop_condition_reached(
+            self.max_measurements,
+            self.max_duration,
+            2 * self.runs_executed * self.number_of_pairs_per_run,
+            self.elapsed(),
+        )
+    }
+
+    /// Executes a single measurement run without considering the stop conditions.
+    fn measurement_run(&mut self) -> MeasurementRunResult {
+        if self.start_time.is_none() {
+            self.start_time = self.clock.now();
+        }
+        self.reporter.run_started(self.runs_executed);
+        self.environment.get_or_insert_with(environment_fingerprint);
+        if let Some(seed) = self.seed {
+            self.rng = run_rng(seed, self.runs_executed);
+        }
+        for i in 0..self.number_of_pairs_per_run {
+            self.inputs[i] = self.specimen.prepare_paired(&mut self.rng);
+            self.a_first[i] = self.rng.gen();
+        }
+
+        for i in 0..self.number_of_pairs_per_run {
+            let (input_a, input_b) = &self.inputs[i];
+            let (first, second) = if self.a_first[i] {
+                (input_a, input_b)
+            } else {
+                (input_b, input_a)
+            };
+            let start_tick = self.cycle_counter.ticks();
+            black_box(self.specimen.do_one_computation(black_box(first)));
+            let middle_tick = self.cycle_counter.ticks();
+            black_box(self.specimen.do_one_computation(black_box(second)));
+            let end_tick = self.cycle_counter.ticks();
+            let policy = TickOverflowPolicy::default();
+            let first_time = policy.execution_time(start_tick, middle_tick);
+            let second_time = policy.execution_time(middle_tick, end_tick);
+            self.execution_times[i] = first_time.zip(second_time).map(|(first, second)| {
+                if self.a_first[i] {
+                    (first, second)
+                } else {
+                    (second, first)
+                }
+            });
+        }
+        self.runs_executed += 1;
+
+        if self.runs_executed > 1 {
+            for (time_a, time_b) in self.execution_times.iter().flatten() {
+                self.test.push(*time_a as f64 - *time_b as f64);
+            }
+        }
+
+        let number_of_measurements = self.test.get_number_of_samples();
+        let max_t = f64::abs(self.test.compute().unwrap_or(0.0));
+        let max_tau = tau(max_t, number_of_measurements);
+        let elapsed = self.elapsed().as_secs_f64();
+        let report = MeasurementReport {
+            max_test: TestKind::Paired,
+            number_of_measurements,
+            max_t,
+            max_tau,
+            t_threshold_overwhelming: self.t_threshold_overwhelming,
+            degrees_of_freedom: self.test.degrees_of_freedom(),
+            p_value: self.test.p_value(),
+            corrected_p_value: None,
+            custom_statistics: Vec::new(),
+            mean_difference_interval: None,
+            throughput: if elapsed == 0.0 {
+                0.0
+            } else {
+                (2 * self.runs_executed * self.number_of_pairs_per_run) as f64 / elapsed
+            },
+            cycle_counter: self.cycle_counter.name(),
+            wall_clock_t: None,
+            wall_clock_leakage_found: None,
+            wall_clock_disagreement: None,
+            overlap_coefficient: None,
+            kolmogorov_smirnov: None,
+            anderson_darling: None,
+            mutual_information: None,
+            timer_calibration: None,
+            computations_per_measurement: 1,
+            discarded_samples: 0,
+            environment: self
+                .environment
+                .clone()
+                .unwrap_or_else(environment_fingerprint),
+        };
+
+        let (enough_measurements, mut summary) =
+            format_measurements(number_of_measurements, self.enough_measurements);
+        let leakage_found = if enough_measurements {
+            summary += &format!(
+                "paired t: {:>7.2}, max tau: {:.2e}, (5/tau)^2: {}. ",
+                max_t,
+                max_tau,
+                format_required_measurements(max_tau)
+            );
+            let (leakage_found, verdict) = grade_t(
+                max_t,
+                self.t_threshold_moderate,
+                self.t_threshold_overwhelming,
+            );
+            summary += verdict;
+            leakage_found
+        } else {
+            false
+        };
+        let result = MeasurementRunResult::graded(leakage_found, report);
+        self.reporter.run_finished(&result, &summary);
+        result
+    }
+}
+
+/// Returns the random number generator of a new context, which is seeded by the operating system.
+/// Without `std`, a fixed seed is used (see `MeasurementContext::with_seed`).
+fn default_rng() -> StdRng {
+    #[cfg(feature = "std")]
+    return StdRng::from_entropy();
+    #[cfg(not(feature = "std"))]
+    return StdRng::seed_from_u64(0);
+}
+
+/// Returns the random number generator of the measurement run `run` of a context with `seed`.
+fn run_rng(seed: u64, run: usize) -> StdRng {
+    // the run number is mixed into the seed, so every run has its own sequence
+    let run = (run as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    StdRng::seed_from_u64(seed ^ run)
+}
+
+/// Returns true, if `computations` reached `max_measurements` or `elapsed` reached `max_duration`.
+fn stop_condition_reached(
+    max_measurements: Option<usize>,
+    max_duration: Option<Duration>,
+    computations: usize,
+    elapsed: Duration,
+) -> bool {
+    let measurements_reached = max_measurements.map_or(false, |max| computations >= max);
+    let duration_reached = max_duration.map_or(false, |max| elapsed >= max);
+    measurements_reached || duration_reached
+}
+
+/// Returns the t value normalized by the square root of the number of measurements.
+fn tau(t: f64, number_of_measurements: f64) -> f64 {
+    if number_of_measurements > 0.0 {
+        t / f64::sqrt(number_of_measurements)
+    } else {
+        0.0
+    }
+}
+
+/// Formats the number of measurements as the start of a summary and returns true, if they are enough
+/// for a verdict. Otherwise, the summary states how many are still to go.
+fn format_measurements(number_of_measurements: f64, enough_measurements: usize) -> (bool, String) {
+    let mut summary = format!("meas: {:>7.2} M, ", (number_of_measurements / 1e6));
+    if number_of_measurements < enough_measurements as f64 {
+        summary += &format!(
+            "not enough measurements ({} still to go).",
+            enough_measurements - (number_of_measurements as usize)
+        );
+        return (false, summary);
+    }
+    (true, summary)
+}
+
+/// Returns true, if `max_t` exceeds one of the thresholds, and the description of the verdict.
+fn grade_t(
+    max_t: f64,
+    t_threshold_moderate: f64,
+    t_threshold_overwhelming: f64,
+) -> (bool, &'static str) {
+    if max_t > t_threshold_overwhelming {
+        (true, "Definitely not constant time.")
+    } else if max_t > t_threshold_moderate {
+        (true, "Probably not constant time.")
+    } else {
+        (false, "For the moment, maybe constant time.")
+    }
+}
+
+/// Returns the reporter of a new context: a [`ConsoleReporter`] with `std`, otherwise a [`SilentReporter`].
+fn default_reporter() -> Box<dyn Reporter> {
+    #[cfg(feature = "std")]
+    return Box::new(ConsoleReporter);
+    #[cfg(not(feature = "std"))]
+    return Box::new(SilentReporter);
+}
+
+/// Executes a function for testing and runs as long as required.
+/// Use `DudectConfig::default()` for the thresholds and the measurement volume of dudect.
+pub fn run_dudect_test<T: MeasurementSpecimen>(specimen: T, config: DudectConfig) -> DudectReport {
+    let mut context = MeasurementContext::new(specimen, config);
+    let result = run_until_verdict(&mut context);
+    context.final_report(&result)
+}
+
+/// Executes the function `compute` for testing with the inputs of `prepare` and runs as long as required.
+/// This is the same as `run_dudect_test` with a [`FnSpecimen`], but without defining a specimen type.
+pub fn run_dudect_fn<P, C, I, O>(prepare: P, compute: C, config: DudectConfig) -> DudectReport
+where
+    P: FnMut(&mut [I], &[bool]),
+    C: FnMut(&I) -> O,
+    I: MeasurementInput,
+{
+    run_dudect_test(FnSpecimen::new(prepare, compute), config)
+}
+
+/// Executes a function for testing until at least `total_computations` computations were executed.
+/// Returns the verdict of the last measurement run.
+pub fn run_dudect_test_budget<T: MeasurementSpecimen>(
+    specimen: T,
+    config: DudectConfig,
+    total_computations: usize,
+) -> MeasurementRunResult {
+    let mut context = MeasurementContext::new(specimen, config);
+    loop {
+        let result = context.execute_measurement_run();
+        if context.computations_executed() >= total_computations {
+            return result;
+        }
+    }
+}
+
+/// Executes measurement runs with an already configured context as long as required.
+pub fn run_context<T: MeasurementSpecimen>(
+    mut context: MeasurementContext<T>,
+) -> MeasurementRunResult {
+    run_until_verdict(&mut context)
+}
+
+/// Executes measurement runs with the context until a verdict is reached.
+fn run_until_verdict<T: MeasurementSpecimen>(
+    context: &mut MeasurementContext<T>,
+) -> MeasurementRunResult {
+    loop {
+        let result = context.execute_measurement_run();
+        if !result.is_no_leakage_evidence_yet() {
+            return result;
+        }
+    }
+}
+
+/// Returns the input for the computation with the given index from the input pool or the input data.
+/// This only borrows the inputs, so the specimen can be borrowed mutably at the same time.
+#[inline(always)]
+fn input_of<'a, I>(
+    input_pool: &'a Option<InputPool<I>>,
+    input_data: &'a [I],
+    index: usize,
+) -> &'a I {
+    match input_pool {
+        Some(pool) => &pool.inputs[pool.indices[index]],
+        None => &input_data[index],
+    }
+}
+
+/// Defines a `#[test]` function `name` that silently executes up to `max_runs` measurement runs with `specimen`
+/// and fails if leakage is found with the threshold `t_threshold` or the timer resolution is insufficient.
+/// Attributes before the name, e.g. `#[should_panic]` or `#[ignore]`, are added to the test function.
+///
+/// ```no_run
+/// use dudect_rs::dudect_test;
+/// use dudect_rs::prelude::*;
+///
+/// struct ThreadSleep {}
+///
+/// impl MeasurementSpecimen for ThreadSleep {
+///     type Input = [u8; 1];
+///     type Output = ();
+///
+///     fn prepare_input_data(&mut self, input_data: &mut [[u8; 1]], is_group_a: &[bool]) {
+///         for (input, is_group_a) in input_data.iter_mut().zip(is_group_a) {
+///             input[0] = if *is_group_a { 10 } else { 0 };
+///         }
+///     }
+///
+///     fn do_one_computation(&mut self, input: &[u8; 1]) {
+///         std::thread::sleep(std::time::Duration::from_micros(input[0] as u64));
+///     }
+/// }
+///
+/// dudect_test!(#[should_panic] thread_sleep_leaks, ThreadSleep {}, 1000, 10.0);
+/// ```
+#[macro_export]
+macro_rules! dudect_test {
+    ($(#[$attribute:meta])* $name:ident, $specimen:expr, $max_runs:expr, $t_threshold:expr) => {
+        #[test]
+        $(#[$attribute])*
+        fn $name() {
+            let config = $crate::dudect::DudectConfig {
+                t_threshold_moderate: $t_threshold,
+                ..$crate::dudect::DudectConfig::default()
+            };
+            let mut context = $crate::dudect::MeasurementContext::new($specimen, config)
+                .with_reporter($crate::report::SilentReporter);
+            for _ in 0..$max_runs {
+                match context.execute_measurement_run() {
+                    $crate::dudect::MeasurementRunResult::LeakageFound(report) => panic!(
+                        "leakage found: max t = {:.2} ({}) reached the threshold {}",
+                        report.max_t, report.max_test, $t_threshold
+                    ),
+                    $crate::dudect::MeasurementRunResult::InsufficientTimerResolution(_) => {
+                        panic!("the timer resolution is insufficient for a verdict")
+                    }
+                    $crate::dudect::MeasurementRunResult::NoLeakageEvidenceYet(_) => {}
+                    _ => break,
+                }
+            }
+        }
+    };
+}
+
+/// Returns `value` unchanged, but prevents the compiler from optimizing based on it, like `core::hint::black_box`
+/// (which is not stable yet). The value is read with a volatile read, so the compiler can neither assume what
+/// the input of a computation is nor remove a computation whose result is passed to this function.
+#[inline(always)]
+pub(crate) fn black_box<T>(value: T) -> T {
+    // SAFETY: `value` is a valid and aligned local, so it can be read; it is forgotten after the bitwise copy,
+    // so it is neither dropped twice nor used again.
+    unsafe {
+        let result = core::ptr::read_volatile(&value);
+        core::mem::forget(value);
+        result
+    }
+}
+
+/// Measures the CPU ticks of a single execution of `f` and returns them together with the result of `f`.
+/// The timestamps are serialized to prevent out-of-order execution across the boundaries of `f`.
+/// This is intended for quick explorations and not for detecting timing leakage.
+#[cfg(feature = "std")]
+pub fn time_once<F: FnOnce() -> R, R>(f: F) -> (u64, R) {
+    let start = serialized_cpu_ticks_begin();
+    let result = f();
+    let end = serialized_cpu_ticks_end();
+    (end.wrapping_sub(start), result)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::report::SilentReporter;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    /// A reporter that records the messages and the summaries.
+    #[derive(Clone, Default)]
+    struct RecordingReporter(Rc<RefCell<Vec<String>>>);
+
+    impl RecordingReporter {
+        fn contains(&self, text: &str) -> bool {
+            self.0.borrow().iter().any(|message| message.contains(text))
+        }
+    }
+
+    impl Reporter for RecordingReporter {
+        fn run_finished(&mut self, _result: &MeasurementRunResult, summary: &str) {
+            self.0.borrow_mut().push(summary.to_string());
+        }
+
+        fn message(&mut self, message: &str) {
+            self.0.borrow_mut().push(message.to_string());
+        }
+    }
+
+    /// A cycle counter that returns the simulated time, which is advanced by the [`Simulated`] specimen.
+    #[derive(Clone, Default)]
+    struct SimulatedCounter(Rc<Cell<u64>>);
+
+    impl SimulatedCounter {
+        fn advance(&self, ticks: u64) {
+            self.0.set(self.0.get().wrapping_add(ticks));
+        }
+    }
+
+    impl CycleCounter for SimulatedCounter {
+        fn ticks(&mut self) -> u64 {
+            self.0.get()
+        }
+    }
+
+    /// A specimen whose computation takes `cost(input)` simulated ticks.
+    /// The first byte of an input is 1 for group a, the other bytes are pseudo-random.
+    struct Simulated<F> {
+        counter: SimulatedCounter,
+        cost: F,
+        state: u64,
+        /// the number of calls of `prepare_input_data`, without the warm-up
+        prepare_calls: Rc<Cell<usize>>,
+    }
+
+    impl<F: FnMut(&[u8; 8]) -> u64> Simulated<F> {
+        fn new(cost: F) -> Self {
+            Self {
+                counter: SimulatedCounter::default(),
+                cost,
+                state: 0x2545_f491_4f6c_dd1d,
+                prepare_calls: Rc::default(),
+            }
+        }
+
+        /// Returns the next value of a xorshift generator, so the inputs do not depend on the context.
+        fn next_random(&mut self) -> u64 {
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 7;
+            self.state ^= self.state << 17;
+            self.state
+        }
+
+        fn prepare(&mut self, input_data: &mut [[u8; 8]], is_group_a: &[bool]) {
+            for (input, is_group_a) in input_data.iter_mut().zip(is_group_a) {
+                *input = self.next_random().to_le_bytes();
+                input[0] = *is_group_a as u8;
+            }
+        }
+    }
+
+    impl<F: FnMut(&[u8; 8]) -> u64> MeasurementSpecimen for Simulated<F> {
+        type Input = [u8; 8];
+        type Output = ();
+
+        fn prepare_input_data(&mut self, input_data: &mut [[u8; 8]], is_group_a: &[bool]) {
+            self.prepare_calls.set(self.prepare_calls.get() + 1);
+            self.prepare(input_data, is_group_a);
+        }
+
+        fn prepare_warmup_data(&mut self, input_data: &mut [[u8; 8]], is_group_a: &[bool]) {
+            self.prepare(input_data, is_group_a);
+        }
+
+        fn do_one_computation(&mut self, input: &[u8; 8]) {
+            self.counter.advance((self.cost)(input));
+        }
+    }
+
+    /// Returns the cost of a computation that is constant time up to some noise.
+    fn constant_cost(input: &[u8; 8]) -> u64 {
+        100 + (input[1] % 8) as u64
+    }
+
+    /// Returns the cost of a computation that is slower for group a.
+    fn leaky_cost(input: &[u8; 8]) -> u64 {
+        constant_cost(input) + 4 * input[0] as u64
+    }
+
+    fn config(number_of_computations_per_run: usize) -> DudectConfig {
+        DudectConfig {
+            number_of_computations_per_run,
+            enough_measurements: 1000,
+            ..DudectConfig::default()
+        }
+    }
+
+    /// Returns a silent context with the simulated counter of `specimen`.
+    fn simulated_context<F: FnMut(&[u8; 8]) -> u64>(
+        specimen: Simulated<F>,
+        config: DudectConfig,
+    ) -> MeasurementContext<Simulated<F>> {
+        let counter = specimen.counter.clone();
+        MeasurementContext::new(specimen, config)
+            .with_cycle_counter(counter)
+            .with_reporter(SilentReporter)
+            .with_seed(1)
+    }
+
+    #[test]
+    fn required_measurements_is_none_without_a_difference() {
+        let mut context = simulated_context(Simulated::new(|_: &[u8; 8]| 100), config(1000));
+        let mut result = context.execute_measurement_run();
+        for _ in 0..5 {
+            result = context.execute_measurement_run();
+        }
+        let report = context.final_report(&result);
+        assert_eq!(report.max_tau, 0.0);
+        assert_eq!(report.required_measurements, None);
+
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000));
+        let result = run_until_verdict(&mut context);
+        let required_measurements = context.final_report(&result).required_measurements;
+        assert!(
+            required_measurements.map_or(false, |required_measurements| {
+                required_measurements.is_finite() && required_measurements > 0.0
+            })
+        );
+    }
+
+    #[test]
+    fn identical_group_inputs_are_reported() {
+        const WARNING: &str = "group inputs appear identical";
+
+        let reporter = RecordingReporter::default();
+        let specimen = FnSpecimen::new(
+            |input_data: &mut [[u8; 8]], _: &[bool]| input_data.fill([7; 8]),
+            |input: &[u8; 8]| input[0],
+        );
+        let mut context =
+            MeasurementContext::new(specimen, config(1000)).with_reporter(reporter.clone());
+        context.execute_measurement_run();
+        assert!(reporter.contains(WARNING));
+
+        let reporter = RecordingReporter::default();
+        let specimen = Simulated::new(constant_cost);
+        let counter = specimen.counter.clone();
+        let mut context = MeasurementContext::new(specimen, config(1000))
+            .with_cycle_counter(counter)
+            .with_reporter(reporter.clone());
+        context.execute_measurement_run();
+        assert!(!reporter.contains(WARNING));
+
+        let reporter = RecordingReporter::default();
+        let specimen = FnSpecimen::new(
+            |_: &mut [Vec<u8>], _: &[bool]| {},
+            |input: &Vec<u8>| input.len(),
+        );
+        let mut context =
+            MeasurementContext::from_initial_input(specimen, config(1000), Vec::new())
+                .with_reporter(reporter.clone());
+        context.execute_measurement_run();
+        assert!(!reporter.contains(WARNING));
+    }
+
+    /// Returns the cost of a computation with a large noise that is shared by the inputs of a pair,
+    /// a small independent noise, and a small leakage of group a.
+    fn shared_noise_cost(input: &[u8; 8]) -> u64 {
+        (input[1] % 100) as u64 + (input[2] % 2) as u64 + 2 * input[0] as u64
+    }
+
+    /// A paired specimen whose inputs only differ in the group and the small independent noise.
+    struct SimulatedPairs {
+        counter: SimulatedCounter,
+    }
+
+    impl PairedMeasurementSpecimen for SimulatedPairs {
+        type Input = [u8; 8];
+        type Output = ();
+
+        fn prepare_paired(&mut self, base_rng: &mut impl RngCore) -> ([u8; 8], [u8; 8]) {
+            let mut input_a = base_rng.next_u64().to_le_bytes();
+            let mut input_b = input_a;
+            input_a[0] = 1;
+            input_b[0] = 0;
+            input_a[2] = base_rng.next_u32() as u8;
+            input_b[2] = base_rng.next_u32() as u8;
+            (input_a, input_b)
+        }
+
+        fn do_one_computation(&mut self, input: &[u8; 8]) {
+            self.counter.advance(shared_noise_cost(input));
+        }
+    }
+
+    #[test]
+    fn pairing_requires_fewer_measurements() {
+        const MAX_RUNS: usize = 1000;
+
+        let counter = SimulatedCounter::default();
+        let specimen = SimulatedPairs {
+            counter: counter.clone(),
+        };
+        let mut paired_context = PairedMeasurementContext::new(specimen, config(500))
+            .with_cycle_counter(counter)
+            .with_reporter(SilentReporter)
+            .with_seed(1);
+        let paired_runs = (1..=MAX_RUNS)
+            .find(|_| {
+                !paired_context
+                    .execute_measurement_run()
+                    .is_no_leakage_evidence_yet()
+            })
+            .unwrap();
+
+        let mut context = simulated_context(Simulated::new(shared_noise_cost), config(1000));
+        let runs = (1..=MAX_RUNS)
+            .find(|_| {
+                !context
+                    .execute_measurement_run()
+                    .is_no_leakage_evidence_yet()
+            })
+            .unwrap();
+
+        // a run of both contexts executes 1000 computations
+        assert!(
+            paired_runs * 4 < runs,
+            "paired: {} runs, unpaired: {} runs",
+            paired_runs,
+            runs
+        );
+    }
+
+    #[test]
+    fn seeded_paired_contexts_are_repeatable() {
+        let paired_t = || {
+            let counter = SimulatedCounter::default();
+            let specimen = SimulatedPairs {
+                counter: counter.clone(),
+            };
+            let mut context = PairedMeasurementContext::new(specimen, config(500))
+                .with_cycle_counter(counter)
+                .with_reporter(SilentReporter)
+                .with_seed(7);
+            for _ in 0..3 {
+                context.execute_measurement_run();
+            }
+            context.paired_t()
+        };
+        assert!(paired_t().is_some());
+        assert_eq!(paired_t(), paired_t());
+    }
+
+    #[test]
+    fn strict_alpha_is_only_crossed_by_a_leak() {
+        const ALPHA: f64 = 1e-9;
+
+        let mut context =
+            simulated_context(Simulated::new(leaky_cost), config(1000)).with_alpha(ALPHA);
+        let result = (0..100)
+            .map(|_| context.execute_measurement_run())
+            .find(|result| !result.is_no_leakage_evidence_yet())
+            .unwrap();
+        assert!(matches!(result, MeasurementRunResult::LeakageFound(_)));
+        assert!(result.report().corrected_p_value.unwrap() < ALPHA);
+
+        let mut context =
+            simulated_context(Simulated::new(constant_cost), config(1000)).with_alpha(ALPHA);
+        for _ in 0..30 {
+            let result = context.execute_measurement_run();
+            assert!(result.is_no_leakage_evidence_yet());
+            assert!(result
+                .report()
+                .corrected_p_value
+                .map_or(true, |p_value| p_value >= ALPHA));
+        }
+    }
+
+    /// A custom test that always finds a difference.
+    #[derive(Default)]
+    struct AlwaysLeaky {
+        number_of_samples: [f64; 2],
+    }
+
+    impl StatisticalTest for AlwaysLeaky {
+        fn push(&mut self, _value: f64, is_sample_group_a: bool) {
+            self.number_of_samples[usize::from(!is_sample_group_a)] += 1.0;
+        }
+
+        fn statistic(&self) -> Option<f64> {
+            Some(100.0)
+        }
+
+        fn get_number_of_samples(&self) -> [f64; 2] {
+            self.number_of_samples
+        }
+
+        fn name(&self) -> &'static str {
+            "always leaky"
+        }
+    }
+
+    #[test]
+    fn alpha_is_graded_before_the_custom_tests() {
+        let reporter = RecordingReporter::default();
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000))
+            .with_alpha(1e-9)
+            .with_statistical_test(AlwaysLeaky::default())
+            .with_reporter(reporter.clone());
+        let result = run_until_verdict(&mut context);
+        assert!(matches!(result, MeasurementRunResult::LeakageFound(_)));
+        assert!(reporter.contains("Not constant time (alpha = 1e-9)."));
+        assert!(!reporter.contains("according to the always leaky test"));
+    }
+
+    /// A custom test with a fixed statistic and threshold.
+    struct FixedStatistic {
+        statistic: f64,
+        threshold: f64,
+        number_of_samples: [f64; 2],
+    }
+
+    impl FixedStatistic {
+        fn new(statistic: f64, threshold: f64) -> Self {
+            Self {
+                statistic,
+                threshold,
+                number_of_samples: [0.0; 2],
+            }
+        }
+    }
+
+    impl StatisticalTest for FixedStatistic {
+        fn push(&mut self, _value: f64, is_sample_group_a: bool) {
+            self.number_of_samples[usize::from(!is_sample_group_a)] += 1.0;
+        }
+
+        fn statistic(&self) -> Option<f64> {
+            Some(self.statistic)
+        }
+
+        fn get_number_of_samples(&self) -> [f64; 2] {
+            self.number_of_samples
+        }
+
+        fn threshold(&self) -> f64 {
+            self.threshold
+        }
+    }
+
+    #[test]
+    fn a_custom_test_can_yield_the_maximum_statistic() {
+        // the custom test yields max t, but stays below its own threshold
+        let mut context = simulated_context(Simulated::new(constant_cost), config(1000))
+            .with_statistical_test(FixedStatistic::new(15.0, 20.0))
+            .with_statistical_test(FixedStatistic::new(16.0, 20.0));
+        for _ in 0..3 {
+            context.execute_measurement_run();
+        }
+        let result = context.execute_measurement_run();
+        assert!(result.is_no_leakage_evidence_yet());
+        let report = result.report();
+        assert_eq!(report.max_test, TestKind::Custom(1));
+        assert_eq!(report.max_t, 16.0);
+        assert!(report.number_of_measurements >= 1000.0);
+        assert_eq!(report.degrees_of_freedom, None);
+        assert_eq!(report.p_value, None);
+
+        let mut context = simulated_context(Simulated::new(constant_cost), config(1000))
+            .with_statistical_test(FixedStatistic::new(15.0, 12.0));
+        let result = run_until_verdict(&mut context);
+        assert!(matches!(result, MeasurementRunResult::LeakageFound(_)));
+        assert_eq!(result.report().max_test, TestKind::Custom(0));
+    }
+
+    /// Returns the result of a computation without side effects that takes some thousand cycles.
+    fn pure_computation(input: &[u8; 8]) -> u64 {
+        (0..2000u64).fold(u64::from_le_bytes(*input), |state, i| {
+            state.rotate_left(5) ^ i.wrapping_mul(state)
+        })
+    }
+
+    /// Returns the mean execution time of `compute` with the default cycle counter.
+    fn mean_execution_time<C: FnMut(&[u8; 8]) -> u64>(compute: C) -> f64 {
+        let specimen = FnSpecimen::new(
+            |input_data: &mut [[u8; 8]], _: &[bool]| {
+                for (i, input) in input_data.iter_mut().enumerate() {
+                    *input = (i as u64).to_le_bytes();
+                }
+            },
+            compute,
+        );
+        let mut context =
+            MeasurementContext::new(specimen, config(1000)).with_reporter(SilentReporter);
+        for _ in 0..3 {
+            context.execute_measurement_run();
+        }
+        let mean = context.snapshot().first_order_uncropped_test.get_mean();
+        (mean[0] + mean[1]) / 2.0
+    }
+
+    #[test]
+    fn computations_are_not_optimized_away() {
+        // the computation has no side effects and can be inlined,
+        // so only consuming its output keeps it in an optimized build
+        let computation = mean_execution_time(pure_computation);
+        let overhead = mean_execution_time(|input| input[0] as u64);
+        assert!(
+            computation > 10.0 * overhead,
+            "computation: {:.0} ticks, overhead: {:.0} ticks",
+            computation,
+            overhead
+        );
+    }
+
+    #[test]
+    fn static_inputs_are_prepared_once() {
+        let specimen = Simulated::new(constant_cost);
+        let prepare_calls = specimen.prepare_calls.clone();
+        let mut context = simulated_context(specimen, config(1000)).with_static_inputs();
+        for _ in 0..5 {
+            context.execute_measurement_run();
+        }
+        assert_eq!(prepare_calls.get(), 1);
+
+        let specimen = Simulated::new(constant_cost);
+        let prepare_calls = specimen.prepare_calls.clone();
+        let mut context = simulated_context(specimen, config(1000));
+        for _ in 0..5 {
+            context.execute_measurement_run();
+        }
+        // all runs but the warm-up run
+        assert_eq!(prepare_calls.get(), 4);
+    }
+
+    #[test]
+    fn run_context_drives_a_configured_context() {
+        let config = DudectConfig {
+            number_of_computations_per_run: 100,
+            enough_measurements: 50,
+            ..DudectConfig::default()
+        };
+        let context = simulated_context(Simulated::new(leaky_cost), config);
+        let result = run_context(context);
+        assert!(matches!(result, MeasurementRunResult::LeakageFound(_)));
+        assert!(result.report().number_of_measurements < 10_000.0);
+
+        let config = DudectConfig {
+            max_measurements: Some(1000),
+            ..config
+        };
+        let context = simulated_context(Simulated::new(constant_cost), config);
+        let result = run_context(context);
+        assert!(matches!(result, MeasurementRunResult::NoLeakageEvidence(_)));
+    }
+
+    thread_local! {
+        /// The number of live [`CountedInput`]s of the current thread.
+        static LIVE_INPUTS: Cell<usize> = Cell::new(0);
+    }
+
+    /// An input that counts its live instances.
+    struct CountedInput(u8);
+
+    impl CountedInput {
+        fn new(value: u8) -> Self {
+            LIVE_INPUTS.with(|live| live.set(live.get() + 1));
+            Self(value)
+        }
+
+        fn live() -> usize {
+            LIVE_INPUTS.with(Cell::get)
+        }
+    }
+
+    impl Clone for CountedInput {
+        fn clone(&self) -> Self {
+            Self::new(self.0)
+        }
+    }
+
+    impl Drop for CountedInput {
+        fn drop(&mut self) {
+            LIVE_INPUTS.with(|live| live.set(live.get() - 1));
+        }
+    }
+
+    impl MeasurementInput for CountedInput {
+        fn initial() -> Self {
+            Self::new(0)
+        }
+    }
+
+    #[test]
+    fn input_pool_memory_scales_with_the_pool_size() {
+        let live_inputs = |number_of_computations_per_run, pool_size: Option<u8>| {
+            let before = CountedInput::live();
+            let specimen = FnSpecimen::new(
+                |_: &mut [CountedInput], _: &[bool]| {},
+                |input: &CountedInput| input.0,
+            );
+            let mut context =
+                MeasurementContext::new(specimen, config(number_of_computations_per_run))
+                    .with_reporter(SilentReporter);
+            if let Some(pool_size) = pool_size {
+                let pool = (0..pool_size).map(CountedInput::new).collect();
+                context = context.with_input_pool(pool, vec![1], vec![0]);
+            }
+            for _ in 0..3 {
+                context.execute_measurement_run();
+            }
+            CountedInput::live() - before
+        };
+        // the context additionally keeps the initial input for resizing the runs
+        assert_eq!(live_inputs(1000, Some(2)), 2 + 1);
+        assert_eq!(live_inputs(10_000, Some(2)), 2 + 1);
+        assert_eq!(live_inputs(10_000, Some(5)), 5 + 1);
+        assert_eq!(live_inputs(1000, None), 1000 + 1);
+        assert_eq!(live_inputs(10_000, None), 10_000 + 1);
+    }
+
+    #[test]
+    fn input_pool_picks_the_inputs_of_each_group() {
+        const N: usize = 1000;
+
+        let pool = (0..4u8)
+            .map(|i| [u8::from(i < 2), i, 0, 0, 0, 0, 0, 0])
+            .collect();
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(N)).with_input_pool(
+            pool,
+            vec![0, 1],
+            vec![2, 3],
+        );
+        context.execute_measurement_run();
+        let pool = context.input_pool.as_ref().unwrap();
+        let mut used = [0; 4];
+        for (index, is_group_a) in pool.indices.iter().zip(&context.is_group_a) {
+            assert_eq!(pool.inputs[*index][0] == 1, *is_group_a);
+            used[*index] += 1;
+        }
+        // the inputs of a group are picked at random
+        assert!(used.iter().all(|count| *count > N / 8));
+    }
+
+    #[test]
+    #[should_panic(expected = "input index 4 is out of range for a pool of size 4")]
+    fn input_pool_rejects_indices_out_of_range() {
+        let pool = vec![[0u8; 8]; 4];
+        let _ = simulated_context(Simulated::new(constant_cost), config(1000)).with_input_pool(
+            pool,
+            vec![0, 1],
+            vec![2, 4],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "each group requires at least one input of the pool")]
+    fn input_pool_rejects_a_group_without_inputs() {
+        let pool = vec![[0u8; 8]; 4];
+        let _ = simulated_context(Simulated::new(constant_cost), config(1000)).with_input_pool(
+            pool,
+            vec![0, 1],
+            Vec::new(),
+        );
+    }
+
+    #[test]
+    fn zero_delta_fraction_only_counts_the_analyzed_execution_times() {
+        const N: usize = 1000;
+
+        // the first 600 computations of every run take no time, but they are discarded
+        let mut calls = 0;
+        let cost = move |_: &[u8; 8]| {
+            calls += 1;
+            if (calls - 1) % N < 600 {
+                0
+            } else {
+                100
+            }
+        };
+        let mut context = simulated_context(Simulated::new(cost), config(N))
+            .with_discarded_samples(600, DISCARDED_TRAILING_SAMPLES);
+        for _ in 0..3 {
+            let result = context.execute_measurement_run();
+            assert!(!matches!(
+                result,
+                MeasurementRunResult::InsufficientTimerResolution(_)
+            ));
+        }
+    }
+
+    /// A timer that only advances by 1000 ticks on every eighth read.
+    #[derive(Default)]
+    struct CoarseCounter {
+        reads: u64,
+    }
+
+    impl CycleCounter for CoarseCounter {
+        fn ticks(&mut self) -> u64 {
+            self.reads += 1;
+            self.reads / 8 * 1000
+        }
+    }
+
+    /// A timer that never advances.
+    struct StuckCounter;
+
+    impl CycleCounter for StuckCounter {
+        fn ticks(&mut self) -> u64 {
+            42
+        }
+    }
+
+    #[test]
+    fn zero_deltas_are_insufficient_timer_resolution() {
+        let reporter = RecordingReporter::default();
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000))
+            .with_cycle_counter(StuckCounter)
+            .with_reporter(reporter.clone());
+        let result = context.execute_measurement_run();
+        assert!(matches!(
+            result,
+            MeasurementRunResult::InsufficientTimerResolution(_)
+        ));
+        assert!(reporter.contains("timer resolution insufficient"));
+
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000))
+            .with_cycle_counter(CoarseCounter::default());
+        let result = run_until_verdict(&mut context);
+        assert!(matches!(
+            result,
+            MeasurementRunResult::InsufficientTimerResolution(_)
+        ));
+    }
+
+    /// Returns the cost of a computation with the same mean for both groups, but a larger variance for group a.
+    fn variance_leaky_cost(input: &[u8; 8]) -> u64 {
+        if input[0] == 1 {
+            // either 70 to 89 or 111 to 130
+            70 + 41 * (input[1] % 2) as u64 + (input[2] % 20) as u64
+        } else {
+            70 + (input[1] % 61) as u64
+        }
+    }
+
+    #[test]
+    fn disabled_second_order_never_yields_max_t() {
+        let mut context = simulated_context(Simulated::new(variance_leaky_cost), config(1000))
+            .with_second_order_min_samples(1000);
+        for _ in 0..30 {
+            context.execute_measurement_run();
+        }
+        assert!(f64::abs(context.snapshot().second_order_test.compute().unwrap()) > 10.0);
+
+        let mut context = simulated_context(Simulated::new(variance_leaky_cost), config(1000))
+            .with_second_order_min_samples(1000)
+            .with_second_order(false);
+        for _ in 0..30 {
+            let result = context.execute_measurement_run();
+            assert_ne!(result.report().max_test, TestKind::SecondOrder);
+            assert_ne!(context.max_test().0, TestKind::SecondOrder);
+        }
+        assert_eq!(
+            context.snapshot().second_order_test.get_number_of_samples(),
+            [0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn time_once_measures_a_busy_loop() {
+        let (ticks, result) = time_once(|| pure_computation(black_box(&[1; 8])));
+        assert!(ticks > 0);
+        assert_eq!(result, pure_computation(&[1; 8]));
+    }
+
+    #[test]
+    fn inputs_are_grouped_by_the_top_bit() {
+        fn top_bit(input: &[u8; 8]) -> bool {
+            input[0] & 0x80 != 0
+        }
+
+        let counter = SimulatedCounter::default();
+        let computation_counter = counter.clone();
+        let mut state = 1u64;
+        let specimen = FnSpecimen::new(
+            move |input_data: &mut [[u8; 8]], _: &[bool]| {
+                for input in input_data {
+                    state = state
+                        .wrapping_mul(6_364_136_223_846_793_005)
+                        .wrapping_add(1);
+                    *input = (state >> 11).to_le_bytes();
+                }
+            },
+            move |input: &[u8; 8]| {
+                computation_counter.advance(constant_cost(input) + 10 * top_bit(input) as u64)
+            },
+        );
+        let mut context = MeasurementContext::new(specimen, config(1000))
+            .with_cycle_counter(counter)
+            .with_reporter(SilentReporter)
+            .with_input_grouping(top_bit);
+        context.execute_measurement_run();
+        for i in 0..context.number_of_computations_per_run() {
+            assert_eq!(context.is_group_a[i], top_bit(context.input(i)));
+        }
+        assert!(context.is_group_a.contains(&true) && context.is_group_a.contains(&false));
+
+        // the leakage of the top bit is attributed to group a
+        let result = run_until_verdict(&mut context);
+        assert!(matches!(result, MeasurementRunResult::LeakageFound(_)));
+        let mean = context.snapshot().first_order_uncropped_test.get_mean();
+        assert!((mean[0] - mean[1] - 10.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn analyzed_indices_exclude_the_batch_boundaries() {
+        // the execution time of each computation is 1000 plus its index
+        let analyzed_execution_times = |discarded_samples: Option<(usize, usize)>| {
+            let counter = SimulatedCounter::default();
+            let computation_counter = counter.clone();
+            let specimen = FnSpecimen::new(
+                |input_data: &mut [[u8; 8]], _: &[bool]| {
+                    for (i, input) in input_data.iter_mut().enumerate() {
+                        *input = (i as u64).to_le_bytes();
+                    }
+                },
+                move |input: &[u8; 8]| {
+                    computation_counter.advance(1000 + u64::from_le_bytes(*input))
+                },
+            );
+            let mut context = MeasurementContext::new(specimen, config(100))
+                .with_cycle_counter(counter)
+                .with_reporter(SilentReporter);
+            if let Some((leading, trailing)) = discarded_samples {
+                context = context.with_discarded_samples(leading, trailing);
+            }
+            context.execute_measurement_run();
+            context.execute_measurement_run();
+            let [a, b] = context.snapshot().first_order_uncropped_test.raw_stats();
+            (
+                context.analyzed_indices(),
+                a.2 + b.2,
+                (a.0 * a.2 + b.0 * b.2).round() as u64,
+                context.discarded_samples(),
+            )
+        };
+        let sum = |indices: core::ops::Range<u64>| indices.map(|i| 1000 + i).sum::<u64>();
+
+        let (indices, number_of_samples, total, discarded) = analyzed_execution_times(None);
+        assert_eq!(
+            indices,
+            DISCARDED_LEADING_SAMPLES..100 - DISCARDED_TRAILING_SAMPLES
+        );
+        assert_eq!(indices, 10..99);
+        assert_eq!(number_of_samples, 89.0);
+        assert_eq!(total, sum(10..99));
+        assert_eq!(discarded, 11);
+
+        let (indices, number_of_samples, total, discarded) = analyzed_execution_times(Some((2, 3)));
+        assert_eq!(indices, 2..97);
+        assert_eq!(number_of_samples, 95.0);
+        assert_eq!(total, sum(2..97));
+        assert_eq!(discarded, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "leave no samples of a run of 100 computations")]
+    fn discarding_all_samples_of_a_run_panics() {
+        let _ = simulated_context(Simulated::new(constant_cost), config(100))
+            .with_discarded_samples(60, 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "leave no samples of a run of 100 computations")]
+    fn resizing_a_run_below_the_discarded_samples_panics() {
+        let mut context = simulated_context(Simulated::new(constant_cost), config(1000))
+            .with_discarded_samples(90, 10);
+        context.resize_runs(100);
+    }
+
+    /// A specimen that prepares different inputs for the warm-up and records the computed inputs.
+    struct WarmupSpecimen {
+        computed: Rc<RefCell<Vec<u8>>>,
+    }
+
+    impl MeasurementSpecimen for WarmupSpecimen {
+        type Input = [u8; 8];
+        type Output = ();
+
+        fn prepare_input_data(&mut self, input_data: &mut [[u8; 8]], _is_group_a: &[bool]) {
+            input_data.fill([0x55; 8]);
+        }
+
+        fn prepare_warmup_data(&mut self, input_data: &mut [[u8; 8]], _is_group_a: &[bool]) {
+            input_data.fill([0xaa; 8]);
+        }
+
+        fn do_one_computation(&mut self, input: &[u8; 8]) {
+            self.computed.borrow_mut().push(input[0]);
+        }
+    }
+
+    #[test]
+    fn warmup_uses_the_warmup_inputs() {
+        let computed = Rc::new(RefCell::new(Vec::new()));
+        let specimen = WarmupSpecimen {
+            computed: computed.clone(),
+        };
+        let mut context = MeasurementContext::new(specimen, config(100))
+            .with_reporter(SilentReporter)
+            .with_warmup_runs(2);
+        for _ in 0..4 {
+            context.execute_measurement_run();
+        }
+        let computed = computed.borrow();
+        assert_eq!(computed.len(), 400);
+        assert!(computed[..200].iter().all(|input| *input == 0xaa));
+        assert!(computed[200..].iter().all(|input| *input == 0x55));
+    }
+
+    #[test]
+    fn enough_measurements_are_collected_over_the_runs() {
+        let config = DudectConfig {
+            enough_measurements: 2500,
+            ..config(1000)
+        };
+        let mut context = simulated_context(Simulated::new(constant_cost), config);
+        assert!(!context.has_enough_measurements());
+        // the warm-up run is not analyzed and each run adds at most 989 measurements
+        for _ in 0..3 {
+            context.execute_measurement_run();
+            assert!(!context.has_enough_measurements());
+        }
+        let flipped = (0..20).any(|_| {
+            context.execute_measurement_run();
+            context.has_enough_measurements()
+        });
+        assert!(flipped);
+        assert!(context.measurements_collected() >= 2500.0);
+    }
+
+    #[test]
+    fn shuffled_execution_keeps_the_group_attribution() {
+        const N: usize = 1000;
+
+        let executed = Rc::new(RefCell::new(Vec::new()));
+        let specimen = Simulated::new({
+            let executed = executed.clone();
+            move |input: &[u8; 8]| {
+                executed.borrow_mut().push(*input);
+                leaky_cost(input)
+            }
+        });
+        let mut context = simulated_context(specimen, config(N)).with_shuffled_execution();
+        for _ in 0..2 {
+            executed.borrow_mut().clear();
+            context.execute_measurement_run();
+            let order = context.execution_order().unwrap().to_vec();
+            let mut sorted = order.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..N).collect::<Vec<_>>());
+            assert_ne!(order, sorted);
+
+            let executed = executed.borrow();
+            assert_eq!(executed.len(), N);
+            for (position, index) in order.into_iter().enumerate() {
+                assert_eq!(executed[position], *context.input(index));
+                assert_eq!(context.is_group_a[index], executed[position][0] == 1);
+                assert_eq!(
+                    context.execution_times[position],
+                    leaky_cost(&executed[position])
+                );
+            }
+        }
+    }
+
+    /// A specimen that records the index, the input and the execution time of each `on_measurement` call.
+    struct InstrumentedSpecimen {
+        counter: SimulatedCounter,
+        measurements: Vec<(usize, [u8; 8], u64)>,
+    }
+
+    impl MeasurementSpecimen for InstrumentedSpecimen {
+        type Input = [u8; 8];
+        type Output = ();
+
+        fn prepare_input_data(&mut self, input_data: &mut [[u8; 8]], is_group_a: &[bool]) {
+            for (i, (input, is_group_a)) in input_data.iter_mut().zip(is_group_a).enumerate() {
+                *input = [*is_group_a as u8, i as u8, 0, 0, 0, 0, 0, 0];
+            }
+        }
+
+        fn do_one_computation(&mut self, input: &[u8; 8]) {
+            self.counter.advance(leaky_cost(input));
+        }
+
+        fn on_measurement(&mut self, index: usize, input: &[u8; 8], delta: u64) {
+            self.measurements.push((index, *input, delta));
+        }
+    }
+
+    #[test]
+    fn on_measurement_is_called_once_per_computation() {
+        const N: usize = 500;
+
+        let counter = SimulatedCounter::default();
+        let specimen = InstrumentedSpecimen {
+            counter: counter.clone(),
+            measurements: Vec::new(),
+        };
+        let mut context = MeasurementContext::new(specimen, config(N))
+            .with_cycle_counter(counter)
+            .with_reporter(SilentReporter)
+            .with_seed(1);
+        for _ in 0..3 {
+            context.specimen.measurements.clear();
+            context.execute_measurement_run();
+            let measurements = &context.specimen.measurements;
+            assert_eq!(measurements.len(), N);
+            for (position, (index, input, delta)) in measurements.iter().enumerate() {
+                assert_eq!(*index, position);
+                assert_eq!(input, context.input(*index));
+                assert_eq!(*delta, leaky_cost(input));
+            }
+        }
+    }
+
+    #[test]
+    fn every_corpus_entry_is_used() {
+        const CORPUS_SIZE: usize = 7;
+
+        let executed = Rc::new(RefCell::new(vec![0; CORPUS_SIZE]));
+        let specimen = Simulated::new({
+            let executed = executed.clone();
+            move |input: &[u8; 8]| {
+                executed.borrow_mut()[input[1] as usize] += 1;
+                leaky_cost(input)
+            }
+        });
+        let counter = specimen.counter.clone();
+        let inputs = (0..CORPUS_SIZE)
+            .map(|i| [(i % 2) as u8, i as u8, 0, 0, 0, 0, 0, 0])
+            .collect();
+        let is_group_a = (0..CORPUS_SIZE).map(|i| i % 2 == 1).collect();
+        let mut context =
+            MeasurementContext::from_inputs(specimen, config(100), inputs, is_group_a)
+                .with_cycle_counter(counter)
+                .with_reporter(SilentReporter);
+        for _ in 0..3 {
+            context.execute_measurement_run();
+            for (i, is_group_a) in context.is_group_a.iter().enumerate() {
+                assert_eq!(*is_group_a, context.input(i)[0] == 1);
+            }
+        }
+        // the corpus is cycled through, so the entries are used equally often
+        let executed = executed.borrow();
+        assert_eq!(executed.iter().sum::<usize>(), 300);
+        assert!(executed.iter().all(|count| *count == 42 || *count == 43));
+    }
+
+    #[test]
+    fn near_maximal_execution_times_are_not_misclassified() {
+        // a counter that went backwards yields a wrapped difference close to u64::MAX, which is dropped
+        assert_eq!(TickOverflowPolicy::Wrap.execution_time(10, 5), None);
+        assert_eq!(TickOverflowPolicy::Wrap.execution_time(0, u64::MAX), None);
+        assert_eq!(
+            TickOverflowPolicy::Wrap.execution_time(0, MAX_EXECUTION_TIME),
+            Some(MAX_EXECUTION_TIME)
+        );
+
+        let mut context = simulated_context(Simulated::new(constant_cost), config(1000));
+        context.execute_measurement_run();
+        let samples = |context: &MeasurementContext<_>| -> Vec<f64> {
+            context
+                .percentile_tests
+                .iter()
+                .map(|test| test.get_number_of_samples().iter().sum())
+                .collect()
+        };
+        let before = samples(&context);
+        // 2^60 and 2^60 + 1 are the same f64, so a comparison of floats would crop all samples
+        context.percentiles.fill((1 << 60) + 1);
+        context.execution_times.fill(1 << 60);
+        context.update_statistics();
+        let analyzed = context.analyzed_indices().len() as f64;
+        for (after, before) in samples(&context).into_iter().zip(before) {
+            assert_eq!(after, before + analyzed);
+        }
+    }
+
+    #[test]
+    fn separate_passes_give_the_group_sample_counts() {
+        let mut context =
+            simulated_context(Simulated::new(leaky_cost), config(1000)).with_separate_passes();
+        // the warm-up run is not analyzed
+        context.execute_measurement_run();
+        let mut expected = [0.0; 2];
+        for _ in 0..3 {
+            context.execute_measurement_run();
+            let order = context.execution_order().unwrap();
+            let number_a = context
+                .is_group_a
+                .iter()
+                .filter(|is_group_a| **is_group_a)
+                .count();
+            // all computations of group a are executed in the first pass
+            assert!(order[..number_a]
+                .iter()
+                .all(|index| context.is_group_a[*index]));
+            assert!(order[number_a..]
+                .iter()
+                .all(|index| !context.is_group_a[*index]));
+            for position in context.analyzed_indices() {
+                expected[!context.is_group_a[order[position]] as usize] += 1.0;
+            }
+            assert_eq!(
+                context.first_order_uncropped_test.get_number_of_samples(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn budget_is_reached_within_one_run() {
+        const BATCH: usize = 1000;
+
+        for budget in [1, 2500, 3000] {
+            let computations = Rc::new(Cell::new(0));
+            let specimen = Simulated::new({
+                let computations = computations.clone();
+                move |input: &[u8; 8]| {
+                    computations.set(computations.get() + 1);
+                    constant_cost(input)
+                }
+            });
+            run_dudect_test_budget(specimen, config(BATCH), budget);
+            // the warm-up run counts towards the budget
+            let computations = computations.get();
+            assert!(
+                computations >= budget && computations < budget + BATCH,
+                "{} computations for a budget of {}",
+                computations,
+                budget
+            );
+        }
+    }
+
+    /// A specimen that records the calls of the warm-up hook and of the input preparation.
+    struct WarmupHookSpecimen {
+        calls: Vec<&'static str>,
+    }
+
+    impl MeasurementSpecimen for WarmupHookSpecimen {
+        type Input = [u8; 8];
+        type Output = ();
+
+        fn prepare_input_data(&mut self, _input_data: &mut [[u8; 8]], _is_group_a: &[bool]) {
+            self.calls.push("prepare");
+        }
+
+        fn prepare_warmup_data(&mut self, _input_data: &mut [[u8; 8]], _is_group_a: &[bool]) {
+            self.calls.push("prepare warm-up");
+        }
+
+        fn do_one_computation(&mut self, _input: &[u8; 8]) {}
+
+        fn warmup(&mut self) {
+            self.calls.push("warmup");
+        }
+    }
+
+    #[test]
+    fn warmup_hook_runs_before_the_first_measured_run() {
+        let specimen = WarmupHookSpecimen { calls: Vec::new() };
+        let mut context = MeasurementContext::new(specimen, config(100))
+            .with_reporter(SilentReporter)
+            .with_warmup_runs(2);
+        for _ in 0..4 {
+            context.execute_measurement_run();
+        }
+        assert_eq!(
+            context.specimen.calls,
+            [
+                "warmup",
+                "prepare warm-up",
+                "prepare warm-up",
+                "prepare",
+                "prepare"
+            ]
+        );
+    }
+
+    #[test]
+    fn throughput_is_positive_and_finite() {
+        let mut context = simulated_context(Simulated::new(constant_cost), config(1000));
+        assert_eq!(context.throughput(), 0.0);
+        for _ in 0..2 {
+            context.execute_measurement_run();
+            let throughput = context.throughput();
+            assert!(throughput.is_finite() && throughput > 0.0, "{}", throughput);
+        }
+        assert_eq!(context.computations_executed(), 2000);
+    }
+
+    #[test]
+    fn the_result_carries_the_report() {
+        let config = config(1000);
+        let mut context = simulated_context(Simulated::new(constant_cost), config);
+        context.execute_measurement_run();
+        match context.execute_measurement_run() {
+            MeasurementRunResult::NoLeakageEvidenceYet(report) => {
+                assert!(
+                    report.max_t < config.t_threshold_moderate,
+                    "{}",
+                    report.max_t
+                );
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let mut context = simulated_context(Simulated::new(leaky_cost), config);
+        match run_until_verdict(&mut context) {
+            MeasurementRunResult::LeakageFound(report) => {
+                assert!(
+                    report.max_t > config.t_threshold_moderate,
+                    "{}",
+                    report.max_t
+                );
+                assert_eq!(
+                    report.t_threshold_overwhelming,
+                    config.t_threshold_overwhelming
+                );
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn wall_clock_statistics_accumulate_alongside_the_ticks() {
+        let mut context =
+            simulated_context(Simulated::new(leaky_cost), config(1000)).with_wall_clock();
+        let mut result = context.execute_measurement_run();
+        assert_eq!(context.wall_clock_t(), None);
+        for _ in 0..3 {
+            result = context.execute_measurement_run();
+        }
+        let wall_clock = context.wall_clock.as_ref().unwrap();
+        let ticks_samples = context.first_order_uncropped_test.get_number_of_samples();
+        assert!(ticks_samples[0] > 1000.0 && ticks_samples[1] > 1000.0);
+        assert_eq!(
+            wall_clock
+                .first_order_uncropped_test
+                .get_number_of_samples(),
+            ticks_samples
+        );
+        assert!(context.first_order_uncropped_t().is_some());
+        assert!(context.wall_clock_t().is_some());
+        assert_eq!(
+            result.report().wall_clock_t,
+            context.wall_clock_t().map(f64::abs)
+        );
+
+        // the simulated ticks leak, so the verdicts disagree, unless the wall-clock times leak as well
+        assert!(context.current_report().max_t > context.t_threshold_moderate);
+        context
+            .wall_clock
+            .as_mut()
+            .unwrap()
+            .first_order_uncropped_test = test_with_t(-0.5);
+        let report = context.current_report();
+        assert_eq!(report.wall_clock_leakage_found, Some(false));
+        assert_eq!(report.wall_clock_disagreement, Some(true));
+        let (_, summary) = context.format_verdict(&report);
+        assert!(summary.contains("The cycle counter and the wall clock disagree"));
+        context
+            .wall_clock
+            .as_mut()
+            .unwrap()
+            .first_order_uncropped_test = test_with_t(-20.0);
+        let report = context.current_report();
+        assert_eq!(report.wall_clock_t, Some(20.0));
+        assert_eq!(report.wall_clock_leakage_found, Some(true));
+        assert_eq!(report.wall_clock_disagreement, Some(false));
+        assert!(simulated_context(Simulated::new(leaky_cost), config(1000))
+            .wall_clock_t()
+            .is_none());
+    }
+
+    #[test]
+    fn an_anomalous_batch_does_not_shift_the_statistics() {
+        let pause = Rc::new(Cell::new(0));
+        let specimen = Simulated::new({
+            let pause = pause.clone();
+            move |input: &[u8; 8]| constant_cost(input) + pause.get()
+        });
+        let mut context =
+            simulated_context(specimen, config(1000)).with_batch_anomaly_rejection(5.0);
+        for _ in 0..6 {
+            context.execute_measurement_run();
+        }
+        let stats = context.first_order_uncropped_test.raw_stats();
+
+        // e.g. the process was migrated to a slower core
+        pause.set(1000);
+        context.execute_measurement_run();
+        assert_eq!(context.rejected_batches(), 1);
+        assert_eq!(context.first_order_uncropped_test.raw_stats(), stats);
+
+        pause.set(0);
+        context.execute_measurement_run();
+        assert_eq!(context.rejected_batches(), 1);
+        let [mean_a, mean_b] = context.first_order_uncropped_test.get_mean();
+        assert!(mean_a < 110.0 && mean_b < 110.0);
+        assert!(context.first_order_uncropped_test.get_number_of_samples()[0] > stats[0].2);
+    }
+
+    #[test]
+    fn batch_anomaly_rejection_requires_a_baseline_and_follows_a_persistent_change() {
+        let mut rejection = BatchAnomalyRejection::new(5.0);
+        // the first batches are accepted, as their spread is not known yet
+        assert!(rejection.accept(100.0));
+        assert!(rejection.accept(100.0));
+        assert!(rejection.accept(1000.0));
+        assert_eq!(rejection.rejected_batches, 0);
+
+        // the spread of identical batch means is at least 1% of their mean
+        let mut rejection = BatchAnomalyRejection::new(5.0);
+        for _ in 0..5 {
+            assert!(rejection.accept(100.0));
+        }
+        assert!(rejection.accept(104.0));
+        assert!(!rejection.accept(110.0));
+
+        // a persistent shift is rejected twice, then it becomes the new baseline
+        assert!(!rejection.accept(200.0));
+        assert!(rejection.accept(200.0));
+        assert_eq!(rejection.rejected_batches, 2);
+        assert_eq!(rejection.number_batches, 1.0);
+        assert_eq!(rejection.mean, 200.0);
+        for _ in 0..4 {
+            assert!(rejection.accept(200.0));
+        }
+        assert!(!rejection.accept(100.0));
+        assert_eq!(rejection.rejected_batches, 3);
+    }
+
+    /// A specimen with two phases, of which only the second one is slower for group a.
+    struct TwoPhaseSpecimen {
+        counter: SimulatedCounter,
+    }
+
+    impl MeasurementSpecimen for TwoPhaseSpecimen {
+        type Input = [u8; 8];
+        type Output = ();
+
+        fn prepare_input_data(&mut self, input_data: &mut [[u8; 8]], is_group_a: &[bool]) {
+            for (i, (input, is_group_a)) in input_data.iter_mut().zip(is_group_a).enumerate() {
+                *input = (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15).to_le_bytes();
+                input[0] = *is_group_a as u8;
+            }
+        }
+
+        fn do_one_computation(&mut self, input: &[u8; 8]) {
+            self.counter.advance(constant_cost(input));
+            self.counter
+                .advance(50 + (input[2] % 8) as u64 + 4 * input[0] as u64);
+        }
+
+        fn do_one_computation_segmented(&mut self, input: &[u8; 8], marks: &mut MarkRecorder) {
+            self.counter.advance(constant_cost(input));
+            marks.mark();
+            self.counter
+                .advance(50 + (input[2] % 8) as u64 + 4 * input[0] as u64);
+        }
+    }
+
+    #[test]
+    fn segmented_analysis_isolates_the_leaky_phase() {
+        let counter = SimulatedCounter::default();
+        let specimen = TwoPhaseSpecimen {
+            counter: counter.clone(),
+        };
+        let config = config(1000);
+        let mut context = MeasurementContext::new(specimen, config)
+            .with_cycle_counter(counter)
+            .with_reporter(SilentReporter)
+            .with_seed(1)
+            .with_segmented_computation();
+        for _ in 0..5 {
+            context.execute_measurement_run();
+        }
+        let t_values = context.segment_t_values();
+        assert_eq!(t_values.len(), 2);
+        let first_phase = t_values[0].unwrap().abs();
+        let second_phase = t_values[1].unwrap().abs();
+        assert!(first_phase < config.t_threshold_moderate, "{}", first_phase);
+        assert!(
+            second_phase > config.t_threshold_moderate,
+            "{}",
+            second_phase
+        );
+        // the marks are taken from the cycle counter of the context
+        let tests = &context.segmented.as_ref().unwrap().tests;
+        assert!(tests[0]
+            .get_mean()
+            .iter()
+            .all(|mean| (100.0..108.0).contains(mean)));
+        assert!(tests[1]
+            .get_mean()
+            .iter()
+            .all(|mean| (50.0..62.0).contains(mean)));
+    }
+
+    #[test]
+    fn each_execution_time_covers_the_computations_per_measurement() {
+        const N: usize = 500;
+
+        let mut context = simulated_context(Simulated::new(constant_cost), config(N))
+            .with_computations_per_measurement(4);
+        let result = context.execute_measurement_run();
+        // each computation of the same input takes 100 to 107 ticks
+        assert!(context.analyzed_times().all(|time| time % 4 == 0));
+        assert!(context
+            .analyzed_times()
+            .all(|time| (400..=428).contains(&time)));
+        assert_eq!(context.computations_executed(), 4 * N);
+        assert_eq!(result.report().computations_per_measurement, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "requires a single computation and repetition per measurement")]
+    fn segmented_computation_rejects_several_computations_per_measurement() {
+        let _ = simulated_context(Simulated::new(constant_cost), config(1000))
+            .with_segmented_computation()
+            .with_computations_per_measurement(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "requires a single computation and repetition per measurement")]
+    fn segmented_computation_rejects_repetitions_per_input() {
+        let _ = simulated_context(Simulated::new(constant_cost), config(1000))
+            .with_repetitions_per_input(3)
+            .with_segmented_computation();
+    }
+
+    #[test]
+    fn streaming_percentiles_follow_all_runs_in_ascending_order() {
+        let offset = Rc::new(Cell::new(0));
+        let specimen = Simulated::new({
+            let offset = offset.clone();
+            move |input: &[u8; 8]| variance_leaky_cost(input) + offset.get()
+        });
+        let mut context = simulated_context(specimen, config(1000)).with_streaming_percentiles();
+        context.execute_measurement_run();
+        // the execution times of the later runs are larger, which moves the thresholds up
+        offset.set(1000);
+        for _ in 0..4 {
+            context.execute_measurement_run();
+            let thresholds: Vec<u64> = context
+                .percentile_iter()
+                .map(|(threshold, _, _)| threshold)
+                .collect();
+            assert!(thresholds.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+        let sketch = context.streaming_percentiles.as_ref().unwrap();
+        assert_eq!(sketch.get_number_of_samples(), 1000 + 4 * 989);
+        // a fifth of the samples are from the warm-up, so the smallest threshold is still below the later runs
+        assert!(context.percentiles[0] < 1000);
+        assert!(context.percentiles[NUMBER_PERCENTILES - 1] > 1000);
+    }
+
+    #[test]
+    fn recomputing_the_percentiles_only_resets_the_percentile_tests() {
+        let offset = Rc::new(Cell::new(0));
+        let specimen = Simulated::new({
+            let offset = offset.clone();
+            move |input: &[u8; 8]| variance_leaky_cost(input) + offset.get()
+        });
+        let mut context =
+            simulated_context(specimen, config(1000)).with_second_order_min_samples(1000);
+        for _ in 0..4 {
+            context.execute_measurement_run();
+        }
+        let first_order = context.first_order_uncropped_test;
+        let second_order = context.second_order_test;
+        assert!(second_order.get_number_of_samples()[0] > 0.0);
+
+        // e.g. the machine got warmer
+        offset.set(100);
+        context.recompute_percentiles();
+        assert!(context
+            .percentile_tests
+            .iter()
+            .all(|test| test.get_number_of_samples() == [0.0, 0.0]));
+        assert_eq!(context.first_order_uncropped_test, first_order);
+        assert_eq!(context.second_order_test, second_order);
+
+        context.execute_measurement_run();
+        assert!(context
+            .percentiles
+            .iter()
+            .all(|threshold| *threshold >= 170));
+        let percentile_samples =
+            context.percentile_tests[context.percentile_tests.len() - 1].get_number_of_samples();
+        assert!(percentile_samples[0] + percentile_samples[1] <= 1000.0);
+        let first_order_samples = context.first_order_uncropped_test.get_number_of_samples();
+        assert!(first_order_samples[0] + first_order_samples[1] > 3000.0);
+    }
+
+    #[test]
+    fn snapshot_contains_all_tests() {
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000));
+        for _ in 0..3 {
+            context.execute_measurement_run();
+        }
+        let snapshot = context.snapshot();
+        assert_eq!(snapshot.percentile_tests.len(), NUMBER_PERCENTILES);
+        assert_eq!(snapshot.percentiles, context.percentiles);
+        assert_eq!(snapshot.runs_executed, 3);
+        assert_eq!(
+            snapshot.first_order_uncropped_test.get_number_of_samples(),
+            context.first_order_uncropped_test.get_number_of_samples()
+        );
+        for (snapshot_test, test) in snapshot
+            .percentile_tests
+            .iter()
+            .zip(&context.percentile_tests)
+        {
+            assert_eq!(snapshot_test, test);
+        }
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&snapshot).unwrap();
+            let restored: CampaignSnapshot = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, snapshot);
+        }
+    }
+
+    #[test]
+    fn a_checkpoint_resumes_the_campaign_with_its_settings() {
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000))
+            .with_alpha(1e-3)
+            .with_multiple_testing_correction(MultipleTestingCorrection::Sidak)
+            .with_first_order_uncropped(false)
+            .with_second_order(false)
+            .with_discarded_samples(2, 3)
+            .with_inclusive_crop(true)
+            .with_batch_anomaly_rejection(5.0);
+        for _ in 0..3 {
+            context.execute_measurement_run();
+        }
+        let saved = context.save_checkpoint();
+        #[cfg(feature = "serde")]
+        let checkpoint: Checkpoint =
+            serde_json::from_str(&serde_json::to_string(&saved).unwrap()).unwrap();
+        #[cfg(not(feature = "serde"))]
+        let checkpoint = saved.clone();
+        assert_eq!(checkpoint, saved);
+
+        let mut resumed = simulated_context(Simulated::new(leaky_cost), DudectConfig::default())
+            .resume_from_checkpoint(checkpoint.clone());
+        assert_eq!(resumed.save_checkpoint(), checkpoint);
+        assert_eq!(resumed.alpha, Some(1e-3));
+        assert_eq!(
+            resumed.multiple_testing_correction,
+            MultipleTestingCorrection::Sidak
+        );
+        assert!(!resumed.first_order_uncropped_enabled && !resumed.second_order_enabled);
+        assert_eq!(resumed.analyzed_indices(), 2..997);
+        assert!(resumed.inclusive_crop);
+        assert_eq!(resumed.batch_anomaly_rejection.unwrap().number_batches, 2.0);
+        assert_eq!(resumed.current_report(), {
+            let mut report = context.current_report();
+            report.throughput = resumed.throughput();
+            report
+        });
+
+        // the resumed campaign is not warmed up again and continues the statistics
+        let samples = resumed.percentile_tests[NUMBER_PERCENTILES - 1].get_number_of_samples();
+        resumed.execute_measurement_run();
+        assert_eq!(resumed.runs_executed(), 4);
+        let resumed_samples =
+            resumed.percentile_tests[NUMBER_PERCENTILES - 1].get_number_of_samples();
+        assert!(resumed_samples[0] + resumed_samples[1] > samples[0] + samples[1]);
+        assert_eq!(
+            resumed.first_order_uncropped_test.get_number_of_samples(),
+            [0.0; 2]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one percentile test is required")]
+    fn a_checkpoint_without_percentiles_is_rejected() {
+        let context = simulated_context(Simulated::new(leaky_cost), config(1000));
+        let mut checkpoint = context.save_checkpoint();
+        checkpoint.config.number_of_percentiles = 0;
+        checkpoint.statistics.percentiles.clear();
+        checkpoint.statistics.percentile_tests.clear();
+        let _ = simulated_context(Simulated::new(leaky_cost), config(1000))
+            .resume_from_checkpoint(checkpoint);
+    }
+
+    #[test]
+    fn a_constant_specimen_gives_a_readable_estimate() {
+        let reporter = RecordingReporter::default();
+        let specimen = Simulated::new(constant_cost);
+        let counter = specimen.counter.clone();
+        // the test with the maximum t value might be cropped to few measurements
+        let config = DudectConfig {
+            enough_measurements: 100,
+            ..config(1000)
+        };
+        let mut context = MeasurementContext::new(specimen, config)
+            .with_cycle_counter(counter)
+            .with_reporter(reporter.clone())
+            .with_seed(1);
+        for _ in 0..5 {
+            context.execute_measurement_run();
+        }
+        let summaries = reporter.0.borrow();
+        let estimates: Vec<&str> = summaries
+            .iter()
+            .filter_map(|summary| summary.split("(5/tau)^2: ").nth(1))
+            .collect();
+        assert!(!estimates.is_empty());
+        for estimate in estimates {
+            assert!(
+                !estimate.contains("inf") && !estimate.contains("NaN") && !estimate.contains("e+"),
+                "{}",
+                estimate
+            );
+        }
+
+        assert_eq!(
+            format_required_measurements(0.0),
+            "no measurable difference"
+        );
+        assert_eq!(
+            format_required_measurements(1e-200),
+            "∞ (no signal detected)"
+        );
+        assert_eq!(format_required_measurements(1e-7), ">1e12");
+        assert_eq!(format_required_measurements(0.01), "2.50e5");
+    }
+
+    #[test]
+    fn second_order_activates_at_the_configured_count() {
+        const MIN_SAMPLES: f64 = 2500.0;
+
+        let mut context = simulated_context(Simulated::new(variance_leaky_cost), config(1000))
+            .with_second_order_min_samples(MIN_SAMPLES as usize);
+        let mut activated = false;
+        for _ in 0..6 {
+            context.execute_measurement_run();
+            let first_order = context.first_order_uncropped_test.get_number_of_samples();
+            let second_order = context.second_order_test.get_number_of_samples();
+            let first_order = first_order[0] + first_order[1];
+            let second_order = second_order[0] + second_order[1];
+            // the second-order test receives each sample after the first MIN_SAMPLES ones
+            assert_eq!(second_order, f64::max(first_order - MIN_SAMPLES, 0.0));
+            activated |= second_order > 0.0;
+        }
+        assert!(activated);
+    }
+
+    #[test]
+    fn percentile_iter_yields_ascending_thresholds() {
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000));
+        for _ in 0..3 {
+            context.execute_measurement_run();
+        }
+        let buckets: Vec<_> = context.percentile_iter().collect();
+        assert_eq!(buckets.len(), NUMBER_PERCENTILES);
+        assert!(buckets.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert!(buckets[0].0 < buckets[NUMBER_PERCENTILES - 1].0);
+        // a larger threshold crops fewer samples
+        assert!(buckets
+            .windows(2)
+            .all(|pair| pair[0].2[0] + pair[0].2[1] <= pair[1].2[0] + pair[1].2[1]));
+        for ((threshold, t, samples), test) in buckets.into_iter().zip(&context.percentile_tests) {
+            assert!(threshold > 0);
+            assert_eq!(t, test.compute());
+            assert_eq!(samples, test.get_number_of_samples());
+        }
+    }
+
+    #[test]
+    fn auto_batch_targets_the_duration_per_run() {
+        let duration_per_run = Duration::from_millis(10);
+        let mut context = simulated_context(Simulated::new(constant_cost), config(1000))
+            .with_auto_batch(duration_per_run);
+        context.execute_measurement_run();
+        // a simulated computation takes far less than 10 µs, which would result in 1000 computations per run
+        let number_of_computations = context.number_of_computations_per_run();
+        assert!(
+            number_of_computations > 1000 && number_of_computations <= AUTO_BATCH_MAX_COMPUTATIONS,
+            "{}",
+            number_of_computations
+        );
+        context.execute_measurement_run();
+        assert_eq!(context.execution_times.len(), number_of_computations);
+
+        let specimen = Simulated::new(|input: &[u8; 8]| {
+            std::thread::sleep(Duration::from_millis(1));
+            constant_cost(input)
+        });
+        let mut context = simulated_context(specimen, config(20)).with_auto_batch(duration_per_run);
+        context.execute_measurement_run();
+        assert_eq!(
+            context.number_of_computations_per_run(),
+            AUTO_BATCH_MIN_COMPUTATIONS
+        );
+    }
+
+    #[test]
+    fn a_shifted_distribution_has_drifted() {
+        let mut reference_context = simulated_context(Simulated::new(constant_cost), config(1000));
+        for _ in 0..2 {
+            reference_context.execute_measurement_run();
+        }
+        let reference =
+            reference_context.execution_times[reference_context.analyzed_indices()].to_vec();
+
+        let mut unchanged_context =
+            simulated_context(Simulated::new(constant_cost), config(1000)).with_seed(2);
+        // e.g. a new version of the function is slower
+        let mut shifted_context = simulated_context(
+            Simulated::new(|input: &[u8; 8]| constant_cost(input) + 3),
+            config(1000),
+        );
+        for _ in 0..2 {
+            unchanged_context.execute_measurement_run();
+            shifted_context.execute_measurement_run();
+        }
+        for test in [DriftTest::KolmogorovSmirnov, DriftTest::TTest] {
+            let unchanged = unchanged_context.compare_to_reference(&reference, test);
+            assert!(!unchanged.drifted, "{:?}", unchanged);
+            let shifted = shifted_context.compare_to_reference(&reference, test);
+            assert!(shifted.drifted, "{:?}", shifted);
+            assert!(shifted.statistic > unchanged.statistic);
+        }
+    }
+
+    #[test]
+    fn history_has_an_entry_per_reporting_run() {
+        let mut context =
+            simulated_context(Simulated::new(leaky_cost), config(1000)).with_history();
+        // the warm-up run does not report a result
+        context.execute_measurement_run();
+        assert!(context.history().is_empty());
+        let mut reports = Vec::new();
+        for _ in 0..5 {
+            let result = context.execute_measurement_run();
+            let report = result.report();
+            reports.push((report.number_of_measurements, report.max_t, report.max_tau));
+        }
+        assert_eq!(context.history(), reports);
+
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000));
+        for _ in 0..3 {
+            context.execute_measurement_run();
+        }
+        assert!(context.history().is_empty());
+    }
+
+    #[test]
+    fn inclusive_crop_populates_the_buckets_at_the_threshold() {
+        // a coarse timer, most execution times are exactly one tick
+        fn coarse_cost(input: &[u8; 8]) -> u64 {
+            if input[1] % 4 == 0 {
+                200
+            } else {
+                100
+            }
+        }
+
+        let lowest_bucket = |inclusive| {
+            let mut context = simulated_context(Simulated::new(coarse_cost), config(1000))
+                .with_inclusive_crop(inclusive);
+            for _ in 0..3 {
+                context.execute_measurement_run();
+            }
+            assert_eq!(context.percentiles[0], 100);
+            let [a, b] = context.percentile_tests[0].get_number_of_samples();
+            a + b
+        };
+        assert_eq!(lowest_bucket(false), 0.0);
+        // about three quarters of the 2 * 989 analyzed samples
+        let samples = lowest_bucket(true);
+        assert!(samples > 1200.0 && samples < 1800.0, "{}", samples);
+    }
+
+    /// Returns a specimen whose computation is slower for group a, if `leaky` is true.
+    fn looping_specimen(leaky: bool) -> impl MeasurementSpecimen {
+        FnSpecimen::new(
+            |input_data: &mut [[u8; 8]], is_group_a: &[bool]| {
+                for (input, is_group_a) in input_data.iter_mut().zip(is_group_a) {
+                    *input = [*is_group_a as u8; 8];
+                }
+            },
+            move |input: &[u8; 8]| {
+                let iterations = if leaky {
+                    100 + 400 * input[0] as u64
+                } else {
+                    100
+                };
+                (0..iterations).fold(0u64, |sum, i| black_box(sum + i))
+            },
+        )
+    }
+
+    dudect_test!(
+        generated_test_passes_for_a_constant_computation,
+        looping_specimen(false),
+        3,
+        10.0
+    );
+
+    dudect_test!(
+        #[should_panic(expected = "leakage found")]
+        generated_test_fails_for_a_leaky_computation,
+        looping_specimen(true),
+        100,
+        10.0
+    );
+
+    #[test]
+    fn batch_recorder_writes_a_file_per_run() {
+        const N: usize = 500;
+
+        let dir = std::env::temp_dir().join(format!("dudect-batches-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(N))
+            .with_batch_recorder(dir.clone());
+        for _ in 0..3 {
+            context.execute_measurement_run();
+        }
+
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        // the runs are numbered from 1
+        assert_eq!(files, ["batch_1.csv", "batch_2.csv", "batch_3.csv"]);
+        let analyzed_samples = N - DISCARDED_LEADING_SAMPLES - DISCARDED_TRAILING_SAMPLES;
+        for file in &files {
+            let content = std::fs::read_to_string(dir.join(file)).unwrap();
+            let mut lines = content.lines();
+            assert_eq!(lines.next(), Some("run,execution_time,is_group_a"));
+            assert_eq!(lines.count(), analyzed_samples);
+        }
+        // the rows of the last run match its analyzed execution times
+        let content = std::fs::read_to_string(dir.join("batch_3.csv")).unwrap();
+        let lines: Vec<&str> = content.lines().skip(1).collect();
+        let expected: Vec<String> = context
+            .analyzed_positions()
+            .map(|i| {
+                format!(
+                    "3,{},{}",
+                    context.execution_times[i],
+                    context.is_group_a[context.input_index(i)]
+                )
+            })
+            .collect();
+        assert_eq!(lines, expected);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// A writer whose buffer can be read while the measurement context owns the writer.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn csv_export_streams_the_analyzed_execution_times_of_all_runs() {
+        const N: usize = 500;
+
+        let buffer = SharedBuffer::default();
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(N))
+            .with_csv_export(buffer.clone());
+        let mut expected = vec!["run,execution_time,is_group_a".to_string()];
+        for _ in 0..3 {
+            context.execute_measurement_run();
+            if context.runs_executed > context.warmup_runs {
+                expected.extend(context.analyzed_positions().map(|i| {
+                    format!(
+                        "{},{},{}",
+                        context.runs_executed,
+                        context.execution_times[i],
+                        context.is_group_a[context.input_index(i)]
+                    )
+                }));
+            }
+        }
+
+        let content = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        // the warm-up run is not exported
+        assert_eq!(
+            lines.len(),
+            1 + 2 * (N - DISCARDED_LEADING_SAMPLES - DISCARDED_TRAILING_SAMPLES)
+        );
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn disabled_first_order_uncropped_never_yields_max_t() {
+        // a shift of all execution times is detected best by the uncropped test
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000));
+        context.execute_measurement_run();
+        let result = context.execute_measurement_run();
+        assert_eq!(result.report().max_test, TestKind::FirstOrderUncropped);
+
+        let mut context = simulated_context(Simulated::new(leaky_cost), config(1000))
+            .with_first_order_uncropped(false);
+        for _ in 0..10 {
+            let result = context.execute_measurement_run();
+            assert_ne!(result.report().max_test, TestKind::FirstOrderUncropped);
+            assert_ne!(context.max_test().0, TestKind::FirstOrderUncropped);
+        }
+        assert_eq!(
+            context.first_order_uncropped_test.get_number_of_samples(),
+            [0.0, 0.0]
+        );
+        assert_eq!(context.first_order_uncropped_t(), None);
+    }
+
+    /// A timer that only advances in steps of `quantum` ticks of the simulated time.
+    struct QuantizedCounter {
+        counter: SimulatedCounter,
+        quantum: u64,
+    }
+
+    impl CycleCounter for QuantizedCounter {
+        fn ticks(&mut self) -> u64 {
+            self.counter.ticks() / self.quantum * self.quantum
+        }
+    }
+
+    #[test]
+    fn timing_resolution_is_the_quantum_of_a_coarse_timer() {
+        const QUANTUM: u64 = 64;
+
+        let reporter = RecordingReporter::default();
+        let specimen = Simulated::new(constant_cost);
+        let counter = QuantizedCounter {
+            counter: specimen.counter.clone(),
+            quantum: QUANTUM,
+        };
+        let mut context = MeasurementContext::new(specimen, config(1000))
+            .with_cycle_counter(counter)
+            .with_reporter(reporter.clone())
+            .with_seed(1);
+        for _ in 0..3 {
+            context.execute_measurement_run();
+            // a computation takes between one and two quanta
+            assert_eq!(context.timing_resolution(), QUANTUM);
+            assert!(context
+                .execution_times
+                .iter()
+                .all(|time| *time == QUANTUM || *time == 2 * QUANTUM));
+        }
+        assert!(reporter.contains("results may be resolution-limited"));
+    }
+
+    #[test]
+    fn tick_overflow_policies_handle_a_wrapped_counter() {
+        // the counter wrapped from u64::MAX - 3 over u64::MAX to 2
+        assert_eq!(
+            TickOverflowPolicy::Wrap.execution_time(u64::MAX - 3, 2),
+            Some(6)
+        );
+        assert_eq!(
+            TickOverflowPolicy::Discard.execution_time(u64::MAX - 3, 2),
+            None
+        );
+        // a counter that went backwards results in an implausibly large wrapped difference, which is dropped
+        assert_eq!(TickOverflowPolicy::Wrap.execution_time(1000, 900), None);
+        assert_eq!(TickOverflowPolicy::Discard.execution_time(1000, 900), None);
+        for policy in [
+            TickOverflowPolicy::Wrap,
+            TickOverflowPolicy::Discard,
+            TickOverflowPolicy::Panic,
+        ] {
+            assert_eq!(policy.execution_time(2, 8), Some(6));
+        }
+
+        // the simulated counter wraps during the measurement run
+        let specimen = Simulated::new(constant_cost);
+        specimen.counter.0.set(u64::MAX - 50_000);
+        let mut context = simulated_context(specimen, config(1000));
+        context.execute_measurement_run();
+        assert_eq!(context.tick_overflows(), 1);
+        assert!(context
+            .execution_times
+            .iter()
+            .all(|time| (100..108).contains(time)));
+    }
+
+    #[test]
+    #[should_panic(expected = "the cycle counter went backwards")]
+    fn panic_policy_panics_on_a_wrapped_counter() {
+        TickOverflowPolicy::Panic.execution_time(u64::MAX - 3, 2);
+    }
+
+    #[test]
+    fn backwards_ticks_are_dropped_from_the_statistics() {
+        for policy in [TickOverflowPolicy::Wrap, TickOverflowPolicy::Discard] {
+            // the counter goes back 1000 ticks in the computation at position 500 of the analyzed run
+            let mut calls = 0;
+            let cost = move |input: &[u8; 8]| {
+                calls += 1;
+                if calls == 1500 {
+                    1000u64.wrapping_neg()
+                } else {
+                    constant_cost(input)
+                }
+            };
+            let mut context = simulated_context(Simulated::new(cost), config(1000))
+                .with_tick_overflow_policy(policy);
+            context.execute_measurement_run();
+            context.execute_measurement_run();
+            assert_eq!(context.tick_overflows(), 1);
+            assert!(context.dropped[499]);
+            assert_eq!(
+                context.dropped.iter().filter(|dropped| **dropped).count(),
+                1
+            );
+            // the dropped execution time is neither in the statistics nor in the percentiles
+            assert_eq!(context.discarded_samples(), 12);
+            let n = context.first_order_uncropped_test.get_number_of_samples();
+            assert_eq!(n[0] + n[1], 988.0);
+            assert!(context
+                .analyzed_times()
+                .all(|time| (100..108).contains(&time)));
+            assert!(context.percentiles.iter().all(|threshold| *threshold < 108));
+        }
+    }
+
+    /// Returns a t-test with 100 samples of variance 1 per group and the t value `t`.
+    fn test_with_t(t: f64) -> TTest {
+        // the standard error of the mean difference is sqrt(1 / 100 + 1 / 100)
+        TTest::from_raw_stats([(t * f64::sqrt(0.02), 99.0, 100.0), (0.0, 99.0, 100.0)])
+    }
+
+    /// Returns a context without samples and with two higher-order tests, whose tests can be replaced.
+    fn max_test_context() -> MeasurementContext<impl MeasurementSpecimen> {
+        let mut context =
+            simulated_context(Simulated::new(constant_cost), config(1000)).with_max_order(4);
+        context.percentiles = (0..NUMBER_PERCENTILES as u64).map(|i| 100 + i).collect();
+        context
+    }
+
+    #[test]
+    fn max_test_compares_absolute_t_values() {
+        let mut context = max_test_context();
+        context.percentile_tests[7] = test_with_t(3.0);
+        context.first_order_uncropped_test = test_with_t(-8.0);
+        let (kind, test) = context.max_test();
+        assert_eq!(kind, TestKind::FirstOrderUncropped);
+        assert!((test.statistic().unwrap() - 8.0).abs() < 1e-9);
+
+        context.second_order_test = test_with_t(-9.0);
+        assert_eq!(context.max_test().0, TestKind::SecondOrder);
+        context.percentile_tests[7] = test_with_t(-10.0);
+        assert_eq!(
+            context.max_test().0,
+            TestKind::Percentile {
+                index: 7,
+                threshold: 107
+            }
+        );
+        context.higher_order_tests[1] = test_with_t(11.0);
+        assert_eq!(context.max_test().0, TestKind::HigherOrder(4));
+    }
+
+    #[test]
+    fn max_test_orders_nan_and_none_below_any_t_value() {
+        let mut context = max_test_context();
+        assert_eq!(test_with_t(f64::NAN).compute().map(f64::is_nan), Some(true));
+        context.percentile_tests[3] = test_with_t(0.5);
+        context.first_order_uncropped_test = test_with_t(f64::NAN);
+        context.second_order_test = test_with_t(f64::NAN);
+        context.higher_order_tests[0] = test_with_t(f64::NAN);
+        // the other percentile tests and higher-order tests are empty, so their t value is None
+        let (kind, test) = context.max_test();
+        assert_eq!(
+            kind,
+            TestKind::Percentile {
+                index: 3,
+                threshold: 103
+            }
+        );
+        assert_eq!(test.statistic(), context.percentile_tests[3].statistic());
+
+        context.percentile_tests[3] = test_with_t(-0.1);
+        assert_eq!(
+            context.max_test().0,
+            TestKind::Percentile {
+                index: 3,
+                threshold: 103
+            }
+        );
+    }
+
+    #[test]
+    fn multiple_testing_corrections_match_known_values() {
+        // 1 - 0.99^5 = 0.0490099501
+        let sidak = MultipleTestingCorrection::Sidak.correct(0.01, 5);
+        assert!((sidak - 0.049_009_950_1).abs() < 1e-12);
+        assert!((MultipleTestingCorrection::Bonferroni.correct(0.01, 5) - 0.05).abs() < 1e-15);
+        // for small p the Šidák correction approaches the Bonferroni correction and does not round to 0
+        let sidak = MultipleTestingCorrection::Sidak.correct(1e-18, 10);
+        assert!((sidak - 1e-17).abs() < 1e-30);
+        assert_eq!(MultipleTestingCorrection::Sidak.correct(0.5, 1), 0.5);
+        assert_eq!(MultipleTestingCorrection::Sidak.correct(1.0, 3), 1.0);
+        assert_eq!(MultipleTestingCorrection::Bonferroni.correct(0.5, 3), 1.0);
+    }
+
+    #[test]
+    fn equivalence_margin_matches_known_values() {
+        // mean difference -1.5 with the standard error 1.0408 and 8.99 degrees of freedom;
+        // the one-sided 95% t quantile is 1.8334, so the 90% confidence interval is [-3.4083, 0.4083]
+        let mut test = TTest::new();
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            test.push(value, true);
+        }
+        for value in [2.0, 3.0, 4.0, 5.0, 6.0, 7.0] {
+            test.push(value, false);
+        }
+        let (lower, upper) = test.mean_difference_confidence_interval(0.9).unwrap();
+        assert!((lower + 3.4083).abs() < 1e-3 && (upper - 0.4083).abs() < 1e-3);
+
+        let mut context = max_test_context();
+        context.enough_measurements = 11;
+        context.first_order_uncropped_test = test;
+        context.equivalence_margin = Some(3.5);
+        assert!(context.is_within_equivalence_margin());
+        context.equivalence_margin = Some(3.4);
+        assert!(!context.is_within_equivalence_margin());
     }
-    upper << 32 | lower
 }
@@ -0,0 +1,139 @@
+use alloc::string::{String, ToString};
+#[cfg(target_arch = "x86_64")]
+use alloc::vec::Vec;
+
+/// Information about the environment in which the measurements were executed.
+/// Values that could not be determined are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentInfo {
+    /// The CPU model as reported by the CPUID brand string.
+    pub cpu_brand: Option<String>,
+    /// True, if the TSC runs at a constant rate regardless of the CPU frequency and power state.
+    pub invariant_tsc: bool,
+    /// The CPU core the process is pinned to. It is only `Some` if the process is allowed to run on a single core,
+    /// not if it is restricted to several cores.
+    pub pinned_core: Option<usize>,
+    /// True, if frequency boosting (turbo) is enabled.
+    pub turbo_enabled: Option<bool>,
+    /// True, if simultaneous multithreading (SMT) is active.
+    pub smt_active: Option<bool>,
+}
+
+/// Collects information about the environment in which the measurements are executed.
+/// The CPU information requires x86_64; the pinning, turbo and SMT information requires Linux and `std`.
+pub fn environment_fingerprint() -> EnvironmentInfo {
+    let (cpu_brand, invariant_tsc) = cpu_identification();
+    let (pinned_core, turbo_enabled, smt_active) = system_configuration();
+    EnvironmentInfo {
+        cpu_brand,
+        invariant_tsc,
+        pinned_core,
+        turbo_enabled,
+        smt_active,
+    }
+}
+
+/// Returns the core the process is pinned to and whether turbo and SMT are enabled, as reported by Linux.
+#[cfg(feature = "std")]
+fn system_configuration() -> (Option<usize>, Option<bool>, Option<bool>) {
+    let read_flag = |path: &str| -> Option<bool> {
+        match std::fs::read_to_string(path).ok()?.trim() {
+            "1" => Some(true),
+            "0" => Some(false),
+            _ => None,
+        }
+    };
+    let pinned_core = std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+                .and_then(single_cpu)
+        });
+    let turbo_enabled = read_flag("/sys/devices/system/cpu/intel_pstate/no_turbo")
+        .map(|no_turbo| !no_turbo)
+        .or_else(|| read_flag("/sys/devices/system/cpu/cpufreq/boost"));
+    let smt_active = read_flag("/sys/devices/system/cpu/smt/active");
+    (pinned_core, turbo_enabled, smt_active)
+}
+
+/// Returns the CPU of a Linux CPU list like `3` or `3-3`, or `None` if it contains several CPUs like `0-3` or `0,2`.
+#[cfg(feature = "std")]
+fn single_cpu(cpu_list: &str) -> Option<usize> {
+    let cpu_list = cpu_list.trim();
+    let (first, last) = cpu_list.split_once('-').unwrap_or((cpu_list, cpu_list));
+    let first = first.parse::<usize>().ok()?;
+    if last.parse::<usize>().ok()? == first {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+/// Returns the core the process is pinned to and whether turbo and SMT are enabled.
+/// They can not be determined without `std`.
+#[cfg(not(feature = "std"))]
+fn system_configuration() -> (Option<usize>, Option<bool>, Option<bool>) {
+    (None, None, None)
+}
+
+/// Returns the brand string of the CPU and whether its time stamp counter is invariant, as reported by CPUID.
+#[cfg(target_arch = "x86_64")]
+fn cpu_identification() -> (Option<String>, bool) {
+    use core::arch::x86_64::__cpuid;
+
+    let max_extended_leaf = unsafe { __cpuid(0x8000_0000) }.eax;
+    let cpu_brand = if max_extended_leaf >= 0x8000_0004 {
+        let mut brand = Vec::with_capacity(48);
+        for leaf in 0x8000_0002..=0x8000_0004 {
+            let registers = unsafe { __cpuid(leaf) };
+            for register in [registers.eax, registers.ebx, registers.ecx, registers.edx] {
+                brand.extend_from_slice(&register.to_le_bytes());
+            }
+        }
+        let brand = String::from_utf8_lossy(&brand);
+        Some(
+            brand
+                .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+                .to_string(),
+        )
+    } else {
+        None
+    };
+    let invariant_tsc =
+        max_extended_leaf >= 0x8000_0007 && unsafe { __cpuid(0x8000_0007) }.edx & (1 << 8) != 0;
+    (cpu_brand, invariant_tsc)
+}
+
+/// Returns the brand string of the CPU and whether its time stamp counter is invariant.
+/// Both are only available on x86_64.
+#[cfg(not(target_arch = "x86_64"))]
+fn cpu_identification() -> (Option<String>, bool) {
+    (None, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn cpu_brand_is_populated() {
+        let brand = environment_fingerprint().cpu_brand.unwrap();
+        assert!(!brand.is_empty());
+        assert_eq!(brand, brand.trim());
+        assert!(!brand.contains('\0'));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn only_a_single_cpu_is_pinned() {
+        assert_eq!(single_cpu("3"), Some(3));
+        assert_eq!(single_cpu(" 5-5\n"), Some(5));
+        assert_eq!(single_cpu("0-3"), None);
+        assert_eq!(single_cpu("0,2"), None);
+        assert_eq!(single_cpu("0-1,4"), None);
+        assert_eq!(single_cpu(""), None);
+    }
+}
@@ -0,0 +1,43 @@
+//! A Rust translation of [dudect](https://github.com/oreparaz/dudect) for detecting timing leakage.
+//!
+//! A function is tested by implementing [`MeasurementSpecimen`] for it and running [`run_dudect_test`],
+//! or by configuring and executing a [`MeasurementContext`] directly.
+//!
+//! Without the default feature `std`, the crate is `no_std` and requires `alloc` and the feature `libm`
+//! for the floating point functions. The reports are then only passed to the [`report::Reporter`]
+//! and the file exports, the wall-clock stop conditions, and the environment information are not available.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(not(feature = "std"), not(feature = "libm")))]
+compile_error!("without the feature `std`, the feature `libm` is required, e.g. `--no-default-features --features libm`");
+
+pub mod dudect;
+pub mod environment;
+#[cfg(not(feature = "std"))]
+mod float;
+pub mod report;
+pub mod statistics;
+pub mod timer;
+
+pub use crate::dudect::{
+    run_dudect_test, DudectConfig, MeasurementContext, MeasurementInput, MeasurementSpecimen,
+};
+pub use crate::statistics::TTest;
+pub use crate::timer::CycleCounter;
+
+/// Re-exports the types that are required for testing a function.
+pub mod prelude {
+    pub use crate::dudect::{
+        run_context, run_dudect_fn, run_dudect_test, DudectConfig, DudectReport, MarkRecorder,
+        MeasurementContext, MeasurementInput, MeasurementReport, MeasurementRunResult,
+        MeasurementSpecimen, Verdict,
+    };
+    #[cfg(feature = "std")]
+    pub use crate::report::ConsoleReporter;
+    pub use crate::report::{Reporter, SilentReporter};
+    pub use crate::statistics::{StatisticalTest, TTest};
+    pub use crate::timer::CycleCounter;
+}
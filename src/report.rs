@@ -0,0 +1,442 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, BufWriter, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::dudect::MeasurementRunResult;
+
+/// Receives the progress and the results of the measurement runs of a context.
+/// All methods do nothing by default.
+pub trait Reporter {
+    /// Called before a measurement run with the number of already executed runs.
+    fn run_started(&mut self, _runs_executed: usize) {}
+    /// Called after a measurement run with its result and a human readable summary.
+    fn run_finished(&mut self, _result: &MeasurementRunResult, _summary: &str) {}
+    /// Called when no more measurement runs are required.
+    fn verdict_reached(&mut self, _result: &MeasurementRunResult) {}
+    /// Called with warnings and information, e.g. about the timer resolution.
+    fn message(&mut self, _message: &str) {}
+}
+
+/// Prints the summaries and messages to stdout (default with `std`).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsoleReporter;
+
+#[cfg(feature = "std")]
+impl Reporter for ConsoleReporter {
+    fn run_finished(&mut self, _result: &MeasurementRunResult, summary: &str) {
+        println!("{}", summary);
+    }
+
+    fn message(&mut self, message: &str) {
+        println!("{}", message);
+    }
+}
+
+/// Discards all reports and messages, e.g. inside a test harness (default without `std`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SilentReporter;
+
+impl Reporter for SilentReporter {}
+
+/// Writes one JSON object per line for each run (`"run"`), message (`"message"`), and final result (`"summary"`).
+/// Non-finite numbers are written as `null`.
+#[cfg(feature = "std")]
+pub struct JsonReporter<W: Write> {
+    writer: W,
+    runs_started: usize,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> JsonReporter<W> {
+    /// Create a new reporter that writes to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            runs_started: 0,
+            error: None,
+        }
+    }
+
+    /// Returns the first error that occurred while writing.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(error) = writeln!(self.writer, "{}", line).and_then(|_| self.writer.flush()) {
+            self.error = Some(error);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Reporter for JsonReporter<W> {
+    fn run_started(&mut self, runs_executed: usize) {
+        self.runs_started = runs_executed + 1;
+    }
+
+    fn run_finished(&mut self, result: &MeasurementRunResult, _summary: &str) {
+        let line = format!(
+            r#"{{"type":"run","run":{},{}}}"#,
+            self.runs_started,
+            json_result_fields(result)
+        );
+        self.write_line(&line);
+    }
+
+    fn verdict_reached(&mut self, result: &MeasurementRunResult) {
+        let line = format!(
+            r#"{{"type":"summary","runs":{},{}}}"#,
+            self.runs_started,
+            json_result_fields(result)
+        );
+        self.write_line(&line);
+    }
+
+    fn message(&mut self, message: &str) {
+        let line = format!(
+            r#"{{"type":"message","message":"{}"}}"#,
+            escape_json(message)
+        );
+        self.write_line(&line);
+    }
+}
+
+/// Writes the results of several specimens as a JUnit XML test suite, e.g. for a CI system.
+/// Leakage is a failure and insufficient timer resolution an error.
+/// It takes the results instead of their `MeasurementReport`, as the report alone does not carry the verdict
+/// (e.g. of the custom tests or the significance level).
+#[cfg(feature = "std")]
+pub fn report_junit<W: Write>(
+    results: &[(String, MeasurementRunResult)],
+    mut w: W,
+) -> io::Result<()> {
+    let count = |predicate: fn(&MeasurementRunResult) -> bool| {
+        results
+            .iter()
+            .filter(|(_, result)| predicate(result))
+            .count()
+    };
+
+    writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        w,
+        r#"<testsuite name="dudect" tests="{}" failures="{}" errors="{}">"#,
+        results.len(),
+        count(|result| matches!(result, MeasurementRunResult::LeakageFound(_))),
+        count(|result| matches!(result, MeasurementRunResult::InsufficientTimerResolution(_)))
+    )?;
+    for (name, result) in results {
+        let report = result.report();
+        let details = format!(
+            "meas: {:.2} M, max t: {:.2} ({}), max tau: {:.2e}. {}",
+            report.number_of_measurements / 1e6,
+            report.max_t,
+            report.max_test,
+            report.max_tau,
+            verdict_text(result)
+        );
+        write!(
+            w,
+            r#"  <testcase name="{}" classname="dudect""#,
+            escape_xml(name)
+        )?;
+        match result {
+            MeasurementRunResult::LeakageFound(_) => {
+                writeln!(w, ">")?;
+                writeln!(
+                    w,
+                    r#"    <failure message="max t: {:.2}">{}</failure>"#,
+                    report.max_t,
+                    escape_xml(&details)
+                )?;
+                writeln!(w, "  </testcase>")?;
+            }
+            MeasurementRunResult::InsufficientTimerResolution(_) => {
+                writeln!(w, ">")?;
+                writeln!(
+                    w,
+                    r#"    <error message="timer resolution insufficient">{}</error>"#,
+                    escape_xml(&details)
+                )?;
+                writeln!(w, "  </testcase>")?;
+            }
+            MeasurementRunResult::NoLeakageEvidenceYet(_)
+            | MeasurementRunResult::NoLeakageEvidence(_)
+            | MeasurementRunResult::ConstantTimeWithinMargin(_) => writeln!(w, "/>")?,
+        }
+    }
+    writeln!(w, "</testsuite>")
+}
+
+/// The header of the CSV export of the analyzed execution times.
+#[cfg(feature = "std")]
+pub(crate) const CSV_EXPORT_HEADER: &str = "run,execution_time,is_group_a";
+
+/// Writes a row `run,execution_time,is_group_a` of the CSV export for each execution time and its group.
+#[cfg(feature = "std")]
+pub(crate) fn write_csv_rows(
+    writer: &mut dyn Write,
+    run: usize,
+    rows: impl Iterator<Item = (u64, bool)>,
+) -> io::Result<()> {
+    for (execution_time, is_group_a) in rows {
+        writeln!(writer, "{},{},{}", run, execution_time, is_group_a)?;
+    }
+    writer.flush()
+}
+
+/// Writes the header and the rows of `write_csv_rows` for the measurement `run` to a new file at `path`.
+#[cfg(feature = "std")]
+pub(crate) fn write_csv_file(
+    path: &Path,
+    run: usize,
+    rows: impl Iterator<Item = (u64, bool)>,
+) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    writeln!(w, "{}", CSV_EXPORT_HEADER)?;
+    write_csv_rows(&mut w, run, rows)
+}
+
+/// Returns a human readable description of the verdict.
+#[cfg(feature = "std")]
+pub(crate) fn verdict_text(result: &MeasurementRunResult) -> &'static str {
+    match result {
+        MeasurementRunResult::LeakageFound(report)
+            if report.max_t > report.t_threshold_overwhelming =>
+        {
+            "Definitely not constant time."
+        }
+        MeasurementRunResult::LeakageFound(_) => "Probably not constant time.",
+        MeasurementRunResult::NoLeakageEvidenceYet(_) => "For the moment, maybe constant time.",
+        MeasurementRunResult::NoLeakageEvidence(_) => "No leakage found, maybe constant time.",
+        MeasurementRunResult::ConstantTimeWithinMargin(_) => {
+            "Constant time within the equivalence margin."
+        }
+        MeasurementRunResult::InsufficientTimerResolution(_) => "Timer resolution insufficient.",
+    }
+}
+
+/// Formats the estimate (5/tau)^2 of the number of measurements required to detect a leakage.
+/// Non-finite or meaninglessly large values are replaced by a description.
+pub(crate) fn format_required_measurements(max_tau: f64) -> String {
+    const MAX_REQUIRED_MEASUREMENTS: f64 = 1e12;
+
+    if max_tau == 0.0 {
+        return "no measurable difference".to_string();
+    }
+    let required_measurements = (5.0 * 5.0) / (max_tau * max_tau);
+    if !required_measurements.is_finite() {
+        "∞ (no signal detected)".to_string()
+    } else if required_measurements > MAX_REQUIRED_MEASUREMENTS {
+        format!(">{:.0e}", MAX_REQUIRED_MEASUREMENTS)
+    } else {
+        format!("{:.2e}", required_measurements)
+    }
+}
+
+/// Escapes the special characters of XML in `text`.
+#[cfg(feature = "std")]
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the JSON fields (without braces) of the statistics and the verdict of `result`.
+#[cfg(feature = "std")]
+fn json_result_fields(result: &MeasurementRunResult) -> String {
+    let report = result.report();
+    let verdict = match result {
+        MeasurementRunResult::LeakageFound(_) => "leakage_found",
+        MeasurementRunResult::NoLeakageEvidenceYet(_) => "no_leakage_evidence_yet",
+        MeasurementRunResult::NoLeakageEvidence(_) => "no_leakage_evidence",
+        MeasurementRunResult::ConstantTimeWithinMargin(_) => "constant_time_within_margin",
+        MeasurementRunResult::InsufficientTimerResolution(_) => "insufficient_timer_resolution",
+    };
+    format!(
+        r#""measurements":{},"max_t":{},"max_tau":{},"max_test":"{}","p_value":{},"verdict":"{}","description":"{}""#,
+        json_number(report.number_of_measurements),
+        json_number(report.max_t),
+        json_number(report.max_tau),
+        escape_json(&report.max_test.to_string()),
+        report
+            .p_value
+            .map_or_else(|| "null".to_string(), json_number),
+        verdict,
+        verdict_text(result)
+    )
+}
+
+/// Formats `value` as a JSON number, or `null` if it is not finite.
+#[cfg(feature = "std")]
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{}", value)
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Escapes the special characters of JSON strings in `text`.
+#[cfg(feature = "std")]
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::dudect::{MeasurementReport, TestKind};
+    use crate::environment::EnvironmentInfo;
+    use alloc::vec::Vec;
+
+    fn report(max_t: f64) -> MeasurementReport {
+        MeasurementReport {
+            max_test: TestKind::FirstOrderUncropped,
+            number_of_measurements: 1e6,
+            max_t,
+            max_tau: max_t / 1e3,
+            t_threshold_overwhelming: 500.0,
+            degrees_of_freedom: None,
+            p_value: None,
+            corrected_p_value: None,
+            custom_statistics: Vec::new(),
+            mean_difference_interval: None,
+            throughput: 1e6,
+            cycle_counter: "test",
+            wall_clock_t: None,
+            wall_clock_leakage_found: None,
+            wall_clock_disagreement: None,
+            overlap_coefficient: None,
+            kolmogorov_smirnov: None,
+            anderson_darling: None,
+            mutual_information: None,
+            timer_calibration: None,
+            computations_per_measurement: 1,
+            discarded_samples: 0,
+            environment: EnvironmentInfo {
+                cpu_brand: None,
+                invariant_tsc: false,
+                pinned_core: None,
+                turbo_enabled: None,
+                smt_active: None,
+            },
+        }
+    }
+
+    #[test]
+    fn junit_failures_match_leaky_specimens() {
+        let results = [
+            (
+                "leaky <a>".to_string(),
+                MeasurementRunResult::LeakageFound(report(20.0)),
+            ),
+            (
+                "constant".to_string(),
+                MeasurementRunResult::NoLeakageEvidence(report(0.5)),
+            ),
+            (
+                "very leaky".to_string(),
+                MeasurementRunResult::LeakageFound(report(1000.0)),
+            ),
+            (
+                "coarse timer".to_string(),
+                MeasurementRunResult::InsufficientTimerResolution(report(0.0)),
+            ),
+        ];
+        let mut xml = Vec::new();
+        report_junit(&results, &mut xml).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+
+        let suite = xml
+            .lines()
+            .find(|line| line.starts_with("<testsuite "))
+            .unwrap();
+        let attribute = |name: &str| -> usize {
+            suite
+                .split(&format!(r#" {}=""#, name))
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        assert_eq!(attribute("tests"), 4);
+        assert_eq!(attribute("failures"), 2);
+        assert_eq!(attribute("errors"), 1);
+        assert_eq!(xml.matches("<testcase ").count(), 4);
+        assert_eq!(xml.matches("<failure ").count(), 2);
+        assert!(xml.contains(r#"<testcase name="leaky &lt;a&gt;" classname="dudect">"#));
+        assert!(xml.contains(r#"<failure message="max t: 1000.00">"#));
+        assert!(xml.trim_end().ends_with("</testsuite>"));
+    }
+
+    #[test]
+    fn verdict_text_uses_the_configured_threshold() {
+        let mut leaky = report(20.0);
+        assert_eq!(
+            verdict_text(&MeasurementRunResult::LeakageFound(leaky.clone())),
+            "Probably not constant time."
+        );
+        leaky.t_threshold_overwhelming = 10.0;
+        assert_eq!(
+            verdict_text(&MeasurementRunResult::LeakageFound(leaky)),
+            "Definitely not constant time."
+        );
+    }
+
+    #[test]
+    fn json_reporter_writes_known_lines() {
+        let mut reporter = JsonReporter::new(Vec::new());
+        reporter.run_started(0);
+        reporter.run_finished(&MeasurementRunResult::LeakageFound(report(20.0)), "");
+        reporter.message("a \"quoted\"\tmessage\n");
+        let mut summary = report(f64::NAN);
+        summary.p_value = Some(0.25);
+        reporter.verdict_reached(&MeasurementRunResult::NoLeakageEvidence(summary));
+        assert!(reporter.take_error().is_none());
+
+        let output = String::from_utf8(reporter.writer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"{"type":"run","run":1,"measurements":1000000,"max_t":20,"max_tau":0.02,"max_test":"first order uncropped","p_value":null,"verdict":"leakage_found","description":"Probably not constant time."}"#,
+                r#"{"type":"message","message":"a \"quoted\"\tmessage\n"}"#,
+                r#"{"type":"summary","runs":1,"measurements":1000000,"max_t":null,"max_tau":null,"max_test":"first order uncropped","p_value":0.25,"verdict":"no_leakage_evidence","description":"No leakage found, maybe constant time."}"#,
+            ]
+        );
+    }
+}
@@ -1,15 +1,169 @@
+// unused if std is linked anyway, e.g. for the test harness
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use crate::float::Float;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The absolute t value above which the tests of this module find a difference between the groups by default.
+pub const DEFAULT_T_THRESHOLD: f64 = 10.0;
+
+/// A two-sample test that detects a difference between the values of group a and b.
+/// It can be implemented for custom detectors, which are fed with the execution times of a measurement context
+/// alongside the built-in tests.
+pub trait StatisticalTest {
+    /// Adds a new value to one of the two sample groups (a or b).
+    /// `is_sample_group_a` is true, if the value belongs to group a.
+    fn push(&mut self, value: f64, is_sample_group_a: bool);
+    /// Returns the absolute value of the test statistic, or `None` if it can not be computed yet.
+    fn statistic(&self) -> Option<f64>;
+    /// Returns the number of values of group a and b.
+    fn get_number_of_samples(&self) -> [f64; 2];
+    /// Returns the degrees of freedom of the test statistic, if the test has any.
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        None
+    }
+    /// Returns the two-sided p-value of the test statistic, if it can be computed.
+    fn p_value(&self) -> Option<f64> {
+        None
+    }
+    /// Returns the threshold of the statistic above which a difference between the groups is found.
+    fn threshold(&self) -> f64 {
+        DEFAULT_T_THRESHOLD
+    }
+    /// Returns the name of the test, which is recorded in the reports.
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+}
+
+impl StatisticalTest for TTest {
+    fn push(&mut self, value: f64, is_sample_group_a: bool) {
+        TTest::push(self, value, is_sample_group_a);
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.compute().map(f64::abs)
+    }
+
+    fn get_number_of_samples(&self) -> [f64; 2] {
+        TTest::get_number_of_samples(self)
+    }
+
+    fn degrees_of_freedom(&self) -> Option<f64> {
+        TTest::degrees_of_freedom(self)
+    }
+
+    fn p_value(&self) -> Option<f64> {
+        TTest::p_value(self)
+    }
+
+    fn name(&self) -> &'static str {
+        "welch"
+    }
+}
+
+impl StatisticalTest for WindowedTTest {
+    fn push(&mut self, value: f64, is_sample_group_a: bool) {
+        WindowedTTest::push(self, value, is_sample_group_a);
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.compute().map(f64::abs)
+    }
+
+    fn get_number_of_samples(&self) -> [f64; 2] {
+        let n = WindowedTTest::get_number_of_samples(self);
+        [n[0] as f64, n[1] as f64]
+    }
+
+    fn name(&self) -> &'static str {
+        "windowed welch"
+    }
+}
+
+impl StatisticalTest for RobustTTest {
+    fn push(&mut self, value: f64, is_sample_group_a: bool) {
+        RobustTTest::push(self, value, is_sample_group_a);
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.compute().map(f64::abs)
+    }
+
+    fn get_number_of_samples(&self) -> [f64; 2] {
+        RobustTTest::get_number_of_samples(self)
+    }
+
+    fn name(&self) -> &'static str {
+        "robust"
+    }
+}
+
+impl StatisticalTest for MannWhitneyUTest {
+    fn push(&mut self, value: f64, is_sample_group_a: bool) {
+        MannWhitneyUTest::push(self, value, is_sample_group_a);
+    }
+
+    fn statistic(&self) -> Option<f64> {
+        self.compute().map(f64::abs)
+    }
+
+    fn get_number_of_samples(&self) -> [f64; 2] {
+        MannWhitneyUTest::get_number_of_samples(self)
+    }
+
+    fn p_value(&self) -> Option<f64> {
+        MannWhitneyUTest::p_value(self)
+    }
+
+    fn name(&self) -> &'static str {
+        "mann-whitney"
+    }
+}
+
 /// Implements a simple Welch's t-test with the Welford method.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TTest {
     groups: [GroupValues; 2],
+    /// If true, the running sums are accumulated with compensated summation.
+    #[cfg_attr(feature = "serde", serde(default))]
+    compensated: bool,
 }
 
 /// GroupValues holds the necessary values for each group sample set.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct GroupValues {
     mean: f64,
     m2: f64,
     number_samples: f64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "extremum::serialize",
+            deserialize_with = "extremum::deserialize_min"
+        )
+    )]
+    min: f64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "extremum::serialize",
+            deserialize_with = "extremum::deserialize_max"
+        )
+    )]
+    max: f64,
+    /// The rounding errors of `mean` and `m2`, if compensated summation is enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    mean_compensation: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    m2_compensation: f64,
 }
 
 impl Default for GroupValues {
@@ -18,18 +172,112 @@ impl Default for GroupValues {
             mean: 0.0,
             m2: 0.0,
             number_samples: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean_compensation: 0.0,
+            m2_compensation: 0.0,
+        }
+    }
+}
+
+/// Serializes the extremes of a group as `None` while they are infinite (without samples),
+/// as formats like JSON can not represent infinite values.
+#[cfg(feature = "serde")]
+mod extremum {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        Some(*value)
+            .filter(|value| value.is_finite())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize_min<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+    }
+
+    pub fn deserialize_max<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NEG_INFINITY))
+    }
+}
+
+impl GroupValues {
+    /// Adds `delta_mean` to the mean and `delta_m2` to m2, with compensated summation if `compensated` is true.
+    fn add(&mut self, delta_mean: f64, delta_m2: f64, compensated: bool) {
+        if compensated {
+            compensated_add(&mut self.mean, &mut self.mean_compensation, delta_mean);
+            compensated_add(&mut self.m2, &mut self.m2_compensation, delta_m2);
+        } else {
+            self.mean += delta_mean;
+            self.m2 += delta_m2;
         }
     }
 }
 
+/// Adds `value` to `sum` with the Kahan-Babuska-Neumaier summation, which accumulates the rounding errors in
+/// `compensation`. The result is renormalized, so that `sum` is always the best approximation of the exact sum
+/// and `compensation` only holds the part that can not be represented.
+fn compensated_add(sum: &mut f64, compensation: &mut f64, value: f64) {
+    let total = *sum + value;
+    let error = if f64::abs(*sum) >= f64::abs(value) {
+        (*sum - total) + value
+    } else {
+        (value - total) + *sum
+    };
+    let corrected_compensation = *compensation + error;
+    let corrected_sum = total + corrected_compensation;
+    *compensation = corrected_compensation - (corrected_sum - total);
+    *sum = corrected_sum;
+}
+
+impl Default for TTest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TTest {
     /// Create a new t-test with empty values.
     pub fn new() -> Self {
         Self {
             groups: [GroupValues::default(); 2],
+            compensated: false,
+        }
+    }
+
+    /// Accumulate the running mean and sum of squared deviations with compensated (Kahan-Babuska-Neumaier) summation.
+    /// This retains the precision over hundreds of millions of samples, e.g. for the second-order test, which pushes
+    /// squared values, at the cost of a slower `push`.
+    #[must_use]
+    pub fn with_compensated_summation(mut self) -> Self {
+        self.compensated = true;
+        self
+    }
+
+    /// Create a t-test from the raw statistics `(mean, m2, number of samples)` of group a and b,
+    /// as returned by [`TTest::raw_stats`].
+    /// The minimum and maximum of the groups are unknown afterwards.
+    pub fn from_raw_stats(stats: [(f64, f64, f64); 2]) -> Self {
+        let group = |(mean, m2, number_samples)| GroupValues {
+            mean,
+            m2,
+            number_samples,
+            ..GroupValues::default()
+        };
+        Self {
+            groups: [group(stats[0]), group(stats[1])],
+            compensated: false,
         }
     }
 
+    /// Returns the raw statistics `(mean, m2, number of samples)` for group a and b.
+    /// m2 is the sum of squared deviations from the mean.
+    /// These are the sufficient statistics of the test, e.g. for transferring it to another process.
+    pub fn raw_stats(&self) -> [(f64, f64, f64); 2] {
+        let raw = |group: GroupValues| (group.mean, group.m2, group.number_samples);
+        [raw(self.groups[0]), raw(self.groups[1])]
+    }
+
     /// Adds a new value to one of the two sample groups (a or b).
     /// Set `is_sample_group_a` to true, if the value belongs to group a.
     pub fn push(&mut self, value: f64, is_sample_group_a: bool) {
@@ -37,9 +285,18 @@ impl TTest {
         let group = &mut self.groups[index];
 
         group.number_samples += 1.0;
-        let delta = value - group.mean;
-        group.mean += delta / group.number_samples;
-        group.m2 += delta * (value - group.mean);
+        // the compensation is zero without compensated summation
+        let delta = value - group.mean - group.mean_compensation;
+        let delta_mean = delta / group.number_samples;
+        let delta_m2 = if self.compensated {
+            // value - (mean + compensation + delta_mean) without rounding the new mean
+            delta * (delta - delta_mean)
+        } else {
+            delta * (value - (group.mean + delta_mean))
+        };
+        group.add(delta_mean, delta_m2, self.compensated);
+        group.min = f64::min(group.min, value);
+        group.max = f64::max(group.max, value);
 
         //assert(class == 0 || class == 1);
         //ctx->n[class]++;
@@ -52,6 +309,34 @@ impl TTest {
         //ctx->m2[class] = ctx->m2[class] + delta * (x - ctx->mean[class]);
     }
 
+    /// Combines the statistics of `other` into this test, as if all its values had been pushed to this test,
+    /// e.g. for partial results that were collected by separate contexts, threads, or machines.
+    /// Uses the parallel variant of the Welford method (Chan et al.).
+    pub fn merge(&mut self, other: &TTest) {
+        for (group, other_group) in self.groups.iter_mut().zip(other.groups.iter()) {
+            if other_group.number_samples == 0.0 {
+                continue;
+            }
+            if group.number_samples == 0.0 {
+                *group = *other_group;
+                continue;
+            }
+
+            let number_samples = group.number_samples + other_group.number_samples;
+            let delta = other_group.mean - group.mean;
+            group.add(
+                delta * other_group.number_samples / number_samples,
+                other_group.m2
+                    + delta * delta * group.number_samples * other_group.number_samples
+                        / number_samples,
+                self.compensated,
+            );
+            group.number_samples = number_samples;
+            group.min = f64::min(group.min, other_group.min);
+            group.max = f64::max(group.max, other_group.max);
+        }
+    }
+
     /// Returns the t value for the test.
     /// If there are no or only one sample available in one of the groups, `None` is returned instead.
     pub fn compute(&self) -> Option<f64> {
@@ -81,6 +366,54 @@ impl TTest {
         //return t_value;
     }
 
+    /// Returns the Welch-Satterthwaite degrees of freedom for the test.
+    /// If there are no or only one sample available in one of the groups, `None` is returned instead.
+    pub fn degrees_of_freedom(&self) -> Option<f64> {
+        let group_a = self.groups[0];
+        let group_b = self.groups[1];
+
+        if group_a.number_samples <= 1.0 || group_b.number_samples <= 1.0 {
+            return None;
+        }
+
+        let var_mean_a = group_a.m2 / (group_a.number_samples - 1.0) / group_a.number_samples;
+        let var_mean_b = group_b.m2 / (group_b.number_samples - 1.0) / group_b.number_samples;
+        let num = (var_mean_a + var_mean_b) * (var_mean_a + var_mean_b);
+        let den = var_mean_a * var_mean_a / (group_a.number_samples - 1.0)
+            + var_mean_b * var_mean_b / (group_b.number_samples - 1.0);
+        if den == 0.0 {
+            None
+        } else {
+            Some(num / den)
+        }
+    }
+
+    /// Returns the two-sided p-value of the test, i.e. the probability of observing an absolute t value
+    /// at least as large as the computed one if both groups have the same mean.
+    /// If the t value or the degrees of freedom can not be computed, `None` is returned instead.
+    pub fn p_value(&self) -> Option<f64> {
+        let t = self.compute()?;
+        let degrees_of_freedom = self.degrees_of_freedom()?;
+        Some(student_t_two_sided_p_value(t, degrees_of_freedom))
+    }
+
+    /// Returns the confidence interval of the difference of the means (a - b) at the `confidence` level,
+    /// e.g. `0.95`, based on the Welch's t-distribution.
+    /// If the degrees of freedom can not be computed, `None` is returned instead.
+    pub fn mean_difference_confidence_interval(&self, confidence: f64) -> Option<(f64, f64)> {
+        let degrees_of_freedom = self.degrees_of_freedom()?;
+        let group_a = self.groups[0];
+        let group_b = self.groups[1];
+        let standard_error = f64::sqrt(
+            group_a.m2 / (group_a.number_samples - 1.0) / group_a.number_samples
+                + group_b.m2 / (group_b.number_samples - 1.0) / group_b.number_samples,
+        );
+        let half_width =
+            student_t_two_sided_quantile(1.0 - confidence, degrees_of_freedom) * standard_error;
+        let difference = group_a.mean - group_b.mean;
+        Some((difference - half_width, difference + half_width))
+    }
+
     /// Returns the number of samples for group a and b.
     pub fn get_number_of_samples(&self) -> [f64; 2] {
         [self.groups[0].number_samples, self.groups[1].number_samples]
@@ -90,4 +423,1529 @@ impl TTest {
     pub fn get_mean(&self) -> [f64; 2] {
         [self.groups[0].mean, self.groups[1].mean]
     }
+
+    /// Returns m2, the sum of squared deviations from the mean, for group a and b.
+    pub fn get_m2(&self) -> [f64; 2] {
+        [self.groups[0].m2, self.groups[1].m2]
+    }
+
+    /// Returns the smallest value for group a and b.
+    /// The value is infinity, if the group has no samples or the minimum is unknown.
+    pub fn get_min(&self) -> [f64; 2] {
+        [self.groups[0].min, self.groups[1].min]
+    }
+
+    /// Returns the largest value for group a and b.
+    /// The value is negative infinity, if the group has no samples or the maximum is unknown.
+    pub fn get_max(&self) -> [f64; 2] {
+        [self.groups[0].max, self.groups[1].max]
+    }
+
+    /// Returns the Grubbs statistic for group a and b, which is the largest deviation of a sample from the mean
+    /// in units of the standard deviation.
+    /// A large value indicates that a single outlier might drive the result of the test.
+    /// The statistic of a group is `None` if it has no or only one sample, no variance, or the minimum and maximum are unknown.
+    pub fn grubbs_statistic(&self) -> [Option<f64>; 2] {
+        let grubbs = |group: &GroupValues| {
+            if group.number_samples <= 1.0 || group.min > group.max {
+                return None;
+            }
+            let standard_deviation = f64::sqrt(group.m2 / (group.number_samples - 1.0));
+            if standard_deviation == 0.0 {
+                return None;
+            }
+            let max_deviation = f64::max(group.max - group.mean, group.mean - group.min);
+            Some(max_deviation / standard_deviation)
+        };
+        [grubbs(&self.groups[0]), grubbs(&self.groups[1])]
+    }
+}
+
+/// Implements a robust analog of the Welch's t-test based on the median and the median absolute deviation (MAD).
+/// The t value is `(median_a - median_b) / sqrt(s_a^2 / n_a + s_b^2 / n_b)` with `s = 1.4826 * MAD`,
+/// which is a consistent estimator of the standard deviation for normal distributions.
+/// In contrast to [`TTest`], all samples are retained, as the median can not be computed incrementally.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobustTTest {
+    groups: [Vec<f64>; 2],
+}
+
+impl RobustTTest {
+    /// Create a new robust t-test with empty values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new value to one of the two sample groups (a or b).
+    /// Set `is_sample_group_a` to true, if the value belongs to group a.
+    pub fn push(&mut self, value: f64, is_sample_group_a: bool) {
+        let index = if is_sample_group_a { 0 } else { 1 };
+        self.groups[index].push(value);
+    }
+
+    /// Returns the robust t value for the test.
+    /// If there are no or only one sample available in one of the groups, `None` is returned instead.
+    pub fn compute(&self) -> Option<f64> {
+        if self.groups[0].len() <= 1 || self.groups[1].len() <= 1 {
+            return None;
+        }
+
+        // scale factor for the MAD to be consistent with the standard deviation of a normal distribution
+        const MAD_SCALE: f64 = 1.4826;
+        let (median_a, mad_a) = median_and_mad(&self.groups[0]);
+        let (median_b, mad_b) = median_and_mad(&self.groups[1]);
+        let scale_a = MAD_SCALE * mad_a;
+        let scale_b = MAD_SCALE * mad_b;
+        let num = median_a - median_b;
+        let den = f64::sqrt(
+            scale_a * scale_a / self.groups[0].len() as f64
+                + scale_b * scale_b / self.groups[1].len() as f64,
+        );
+        if den == 0.0 {
+            None
+        } else {
+            Some(num / den)
+        }
+    }
+
+    /// Returns the number of samples for group a and b.
+    pub fn get_number_of_samples(&self) -> [f64; 2] {
+        [self.groups[0].len() as f64, self.groups[1].len() as f64]
+    }
+}
+
+/// Implements the nonparametric Mann-Whitney U test (Wilcoxon rank-sum test) with the normal approximation.
+/// It tests whether a value of one group tends to be larger than a value of the other group and does not assume
+/// normally distributed values, which makes it suitable for the skewed distributions of execution times.
+/// Like [`RobustTTest`], all samples are retained, as the ranks can not be computed incrementally.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MannWhitneyUTest {
+    groups: [Vec<f64>; 2],
+}
+
+impl MannWhitneyUTest {
+    /// Create a new Mann-Whitney U test with empty values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new value to one of the two sample groups (a or b).
+    /// Set `is_sample_group_a` to true, if the value belongs to group a.
+    pub fn push(&mut self, value: f64, is_sample_group_a: bool) {
+        let index = if is_sample_group_a { 0 } else { 1 };
+        self.groups[index].push(value);
+    }
+
+    /// Returns the U statistic of group a, i.e. the number of pairs in which the value of group a is larger
+    /// than the value of group b, with ties counted as one half.
+    /// If one of the groups is empty, `None` is returned instead.
+    pub fn u_statistic(&self) -> Option<f64> {
+        self.rank_sum_and_ties()
+            .map(|(rank_sum_a, _)| rank_sum_a - self.n(0) * (self.n(0) + 1.0) / 2.0)
+    }
+
+    /// Returns the standardized U statistic (z value), which is approximately standard normal distributed
+    /// if both groups have the same distribution. The variance is corrected for ties.
+    /// If there are no or only one sample available in one of the groups or all values are equal,
+    /// `None` is returned instead.
+    pub fn compute(&self) -> Option<f64> {
+        if self.groups[0].len() <= 1 || self.groups[1].len() <= 1 {
+            return None;
+        }
+
+        let (rank_sum_a, tie_correction) = self.rank_sum_and_ties()?;
+        let (n_a, n_b) = (self.n(0), self.n(1));
+        let n = n_a + n_b;
+        let u = rank_sum_a - n_a * (n_a + 1.0) / 2.0;
+        let mean = n_a * n_b / 2.0;
+        let variance = n_a * n_b / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+        if variance <= 0.0 {
+            None
+        } else {
+            Some((u - mean) / f64::sqrt(variance))
+        }
+    }
+
+    /// Returns the two-sided p-value of the test with the normal approximation, if the z value can be computed.
+    pub fn p_value(&self) -> Option<f64> {
+        self.compute().map(normal_two_sided_p_value)
+    }
+
+    /// Returns the number of samples for group a and b.
+    pub fn get_number_of_samples(&self) -> [f64; 2] {
+        [self.n(0), self.n(1)]
+    }
+
+    fn n(&self, index: usize) -> f64 {
+        self.groups[index].len() as f64
+    }
+
+    /// Returns the sum of the ranks of group a and the sum of `t^3 - t` over all groups of `t` tied values.
+    /// Tied values get the average of their ranks.
+    fn rank_sum_and_ties(&self) -> Option<(f64, f64)> {
+        if self.groups[0].is_empty() || self.groups[1].is_empty() {
+            return None;
+        }
+
+        let mut values: Vec<(f64, bool)> = self.groups[0]
+            .iter()
+            .map(|value| (*value, true))
+            .chain(self.groups[1].iter().map(|value| (*value, false)))
+            .collect();
+        values.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        let mut rank_sum_a = 0.0;
+        let mut tie_correction = 0.0;
+        let mut start = 0;
+        while start < values.len() {
+            let mut end = start + 1;
+            while end < values.len() && values[end].0 == values[start].0 {
+                end += 1;
+            }
+            // the ranks start at 1
+            let average_rank = (start + end + 1) as f64 / 2.0;
+            let ties = (end - start) as f64;
+            let ties_a = values[start..end].iter().filter(|value| value.1).count();
+            rank_sum_a += average_rank * ties_a as f64;
+            tie_correction += ties * ties * ties - ties;
+            start = end;
+        }
+        Some((rank_sum_a, tie_correction))
+    }
+}
+
+/// Returns the median and the median absolute deviation of the non-empty `data`.
+fn median_and_mad(data: &[f64]) -> (f64, f64) {
+    fn median(sorted: &[f64]) -> f64 {
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        }
+    }
+
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let median_value = median(&sorted);
+    let mut deviations: Vec<f64> = sorted
+        .iter()
+        .map(|value| (value - median_value).abs())
+        .collect();
+    deviations.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    (median_value, median(&deviations))
+}
+
+/// Combines the p-values of several tests into a single p-value with Fisher's method.
+/// The statistic `-2 * sum(ln p_i)` follows a chi-square distribution with `2k` degrees of freedom
+/// for `k` independent tests.
+/// Returns `None` if there are no p-values.
+pub fn fisher_combined_p_value(p_values: &[f64]) -> Option<f64> {
+    if p_values.is_empty() {
+        return None;
+    }
+
+    // half of the chi-square statistic
+    let lambda = -p_values.iter().map(|p| p.ln()).sum::<f64>();
+    if lambda.is_infinite() {
+        return Some(0.0);
+    }
+    // the survival function of the chi-square distribution with 2k degrees of freedom
+    // is the cumulative distribution function of a Poisson distribution with mean lambda at k - 1
+    let p_value = (0..p_values.len())
+        .map(|i| (-lambda + i as f64 * lambda.ln() - ln_gamma(i as f64 + 1.0)).exp())
+        .sum::<f64>();
+    Some(f64::min(p_value, 1.0))
+}
+
+/// Returns the two-sided p-value for the value `t` of a Student's t-distribution.
+fn student_t_two_sided_p_value(t: f64, degrees_of_freedom: f64) -> f64 {
+    regularized_incomplete_beta(
+        degrees_of_freedom / 2.0,
+        0.5,
+        degrees_of_freedom / (degrees_of_freedom + t * t),
+    )
+}
+
+/// Returns the two-sided p-value for the value `z` of a standard normal distribution.
+fn normal_two_sided_p_value(z: f64) -> f64 {
+    complementary_error_function(f64::abs(z) / core::f64::consts::SQRT_2)
+}
+
+/// Returns the complementary error function for `x >= 0` (Chebyshev approximation with a relative error
+/// below 1.2e-7, see Numerical Recipes, `erfcc`).
+fn complementary_error_function(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x);
+    let polynomial = -x * x - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    f64::min(t * f64::exp(polynomial), 1.0)
+}
+
+/// Returns the positive value `t` of a Student's t-distribution with the two-sided p-value `p_value`
+/// (found by bisection).
+fn student_t_two_sided_quantile(p_value: f64, degrees_of_freedom: f64) -> f64 {
+    let mut low = 0.0;
+    let mut high = 1.0;
+    while student_t_two_sided_p_value(high, degrees_of_freedom) > p_value && high < 1e12 {
+        high *= 2.0;
+    }
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if student_t_two_sided_p_value(middle, degrees_of_freedom) > p_value {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Returns the natural logarithm of the gamma function (Lanczos approximation, g = 7).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // reflection formula
+        core::f64::consts::PI.ln() - (core::f64::consts::PI * x).sin().ln() - ln_gamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let mut sum = COEFFICIENTS[0];
+        for (i, coefficient) in COEFFICIENTS.iter().enumerate().skip(1) {
+            sum += coefficient / (x + i as f64);
+        }
+        let t = x + 7.5;
+        0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+    }
+}
+
+/// Returns the regularized incomplete beta function I_x(a, b).
+fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    // the continued fraction converges quickly only for x < (a + 1) / (a + b + 2), use the symmetry otherwise
+    if x < (a + 1.0) / (a + b + 2.0) {
+        ln_front.exp() * incomplete_beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - ln_front.exp() * incomplete_beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Evaluates the continued fraction for the incomplete beta function with the modified Lentz's method.
+fn incomplete_beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 300;
+    const EPSILON: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        // even step
+        let numerator = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        result *= d * c;
+
+        // odd step
+        let numerator = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        result *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    result
+}
+
+/// Implements a Welch's t-test that only considers the last `window_size` samples of each group.
+/// In contrast to [`TTest`], the raw samples of the window are retained.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowedTTest {
+    window_size: usize,
+    groups: [VecDeque<f64>; 2],
+}
+
+impl WindowedTTest {
+    /// Create a new windowed t-test that retains at most `window_size` samples per group.
+    pub fn new(window_size: usize) -> Self {
+        Self {
+            window_size,
+            groups: [
+                VecDeque::with_capacity(window_size),
+                VecDeque::with_capacity(window_size),
+            ],
+        }
+    }
+
+    /// Adds a new value to one of the two sample groups (a or b).
+    /// If the window of the group is full, the oldest value of that group is discarded.
+    /// Set `is_sample_group_a` to true, if the value belongs to group a.
+    pub fn push(&mut self, value: f64, is_sample_group_a: bool) {
+        let index = if is_sample_group_a { 0 } else { 1 };
+        let group = &mut self.groups[index];
+
+        if group.len() == self.window_size {
+            group.pop_front();
+        }
+        if self.window_size > 0 {
+            group.push_back(value);
+        }
+    }
+
+    /// Returns the t value for the samples currently in the window.
+    /// If there are no or only one sample available in one of the groups, `None` is returned instead.
+    pub fn compute(&self) -> Option<f64> {
+        let mut test = TTest::new();
+        for value in &self.groups[0] {
+            test.push(*value, true);
+        }
+        for value in &self.groups[1] {
+            test.push(*value, false);
+        }
+        test.compute()
+    }
+
+    /// Returns the number of samples currently in the window for group a and b.
+    pub fn get_number_of_samples(&self) -> [usize; 2] {
+        [self.groups[0].len(), self.groups[1].len()]
+    }
+}
+
+/// Implements a paired t-test with the Welford method, i.e. a one-sample t-test of the differences
+/// of paired samples against zero.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct PairedTTest {
+    differences: GroupValues,
+}
+
+impl Default for PairedTTest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PairedTTest {
+    /// Create a new paired t-test with empty values.
+    pub fn new() -> Self {
+        Self {
+            differences: GroupValues::default(),
+        }
+    }
+
+    /// Adds the difference of a pair of samples (value of group a minus value of group b).
+    pub fn push(&mut self, difference: f64) {
+        let group = &mut self.differences;
+
+        group.number_samples += 1.0;
+        let delta = difference - group.mean;
+        group.mean += delta / group.number_samples;
+        group.m2 += delta * (difference - group.mean);
+        group.min = f64::min(group.min, difference);
+        group.max = f64::max(group.max, difference);
+    }
+
+    /// Returns the t value for the test.
+    /// If there are less than two pairs or all differences are equal, `None` is returned instead.
+    pub fn compute(&self) -> Option<f64> {
+        let group = self.differences;
+        if group.number_samples <= 1.0 {
+            return None;
+        }
+
+        let var = group.m2 / (group.number_samples - 1.0);
+        let den = f64::sqrt(var / group.number_samples);
+        if den == 0.0 {
+            None
+        } else {
+            Some(group.mean / den)
+        }
+    }
+
+    /// Returns the degrees of freedom for the test, i.e. the number of pairs minus one.
+    /// If there are less than two pairs, `None` is returned instead.
+    pub fn degrees_of_freedom(&self) -> Option<f64> {
+        if self.differences.number_samples <= 1.0 {
+            None
+        } else {
+            Some(self.differences.number_samples - 1.0)
+        }
+    }
+
+    /// Returns the two-sided p-value of the test, i.e. the probability of observing an absolute t value
+    /// at least as large as the computed one if the mean difference is zero.
+    /// If the t value can not be computed, `None` is returned instead.
+    pub fn p_value(&self) -> Option<f64> {
+        let t = self.compute()?;
+        let degrees_of_freedom = self.degrees_of_freedom()?;
+        Some(student_t_two_sided_p_value(t, degrees_of_freedom))
+    }
+
+    /// Returns the number of pairs.
+    pub fn get_number_of_samples(&self) -> f64 {
+        self.differences.number_samples
+    }
+}
+
+/// Holds a uniform random sample of bounded size of all values pushed to each of the two groups (reservoir sampling).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reservoir {
+    size: usize,
+    groups: [Vec<u64>; 2],
+    /// The number of values that were pushed to each group.
+    number_seen: [u64; 2],
+    rng: StdRng,
+}
+
+impl Reservoir {
+    /// Create a new reservoir that holds at most `size` values per group.
+    /// Without `std`, the replaced values are chosen with a fixed seed.
+    pub fn new(size: usize) -> Self {
+        #[cfg(feature = "std")]
+        let rng = StdRng::from_entropy();
+        #[cfg(not(feature = "std"))]
+        let rng = StdRng::seed_from_u64(0);
+        Self {
+            size,
+            groups: [Vec::with_capacity(size), Vec::with_capacity(size)],
+            number_seen: [0; 2],
+            rng,
+        }
+    }
+
+    /// Adds a new value to one of the two sample groups (a or b).
+    /// If the reservoir of the group is full, the value replaces a random value with the appropriate probability.
+    /// Set `is_sample_group_a` to true, if the value belongs to group a.
+    pub fn push(&mut self, value: u64, is_sample_group_a: bool) {
+        let index = if is_sample_group_a { 0 } else { 1 };
+        let group = &mut self.groups[index];
+
+        self.number_seen[index] += 1;
+        if group.len() < self.size {
+            group.push(value);
+        } else {
+            let replace_index = self.rng.gen_range(0..self.number_seen[index]);
+            if replace_index < self.size as u64 {
+                group[replace_index as usize] = value;
+            }
+        }
+    }
+
+    /// Returns the sampled values of group a and b.
+    pub fn get_samples(&self) -> (&[u64], &[u64]) {
+        (&self.groups[0], &self.groups[1])
+    }
+}
+
+/// Returns the effect size (Cohen's d with pooled standard deviation) of the samples `a` and `b`
+/// and its standard error estimated with the jackknife (leave-one-out) method.
+/// If there are less than two samples in one of the groups, `(NaN, NaN)` is returned.
+pub fn jackknife_effect_size(a: &[u64], b: &[u64]) -> (f64, f64) {
+    if a.len() < 2 || b.len() < 2 {
+        return (f64::NAN, f64::NAN);
+    }
+
+    // shift all values by the overall mean to reduce the cancellation in the sum of squares
+    let shift =
+        a.iter().chain(b).map(|value| *value as f64).sum::<f64>() / (a.len() + b.len()) as f64;
+    let sums = |data: &[u64]| {
+        data.iter()
+            .map(|value| *value as f64 - shift)
+            .fold((0.0, 0.0), |(sum, squares), value| {
+                (sum + value, squares + value * value)
+            })
+    };
+    let (sum_a, squares_a) = sums(a);
+    let (sum_b, squares_b) = sums(b);
+    let n_a = a.len() as f64;
+    let n_b = b.len() as f64;
+
+    let effect_size = cohens_d(sum_a, squares_a, n_a, sum_b, squares_b, n_b);
+    let leave_one_out: Vec<f64> = a
+        .iter()
+        .map(|value| {
+            let value = *value as f64 - shift;
+            cohens_d(
+                sum_a - value,
+                squares_a - value * value,
+                n_a - 1.0,
+                sum_b,
+                squares_b,
+                n_b,
+            )
+        })
+        .chain(b.iter().map(|value| {
+            let value = *value as f64 - shift;
+            cohens_d(
+                sum_a,
+                squares_a,
+                n_a,
+                sum_b - value,
+                squares_b - value * value,
+                n_b - 1.0,
+            )
+        }))
+        .collect();
+
+    let n = leave_one_out.len() as f64;
+    let mean = leave_one_out.iter().sum::<f64>() / n;
+    let squared_deviations: f64 = leave_one_out.iter().map(|d| (d - mean) * (d - mean)).sum();
+    let standard_error = f64::sqrt((n - 1.0) / n * squared_deviations);
+    (effect_size, standard_error)
+}
+
+/// Returns Cohen's d computed from the sums and the sums of squares of both groups.
+fn cohens_d(sum_a: f64, squares_a: f64, n_a: f64, sum_b: f64, squares_b: f64, n_b: f64) -> f64 {
+    let mean_a = sum_a / n_a;
+    let mean_b = sum_b / n_b;
+    let squared_deviations_a = squares_a - sum_a * mean_a;
+    let squared_deviations_b = squares_b - sum_b * mean_b;
+    let pooled_standard_deviation =
+        f64::sqrt((squared_deviations_a + squared_deviations_b) / (n_a + n_b - 2.0));
+    (mean_a - mean_b) / pooled_standard_deviation
+}
+
+/// Computes the two-sample Kolmogorov-Smirnov test of the samples `a` and `b`.
+/// Returns the statistic (the largest distance between the empirical distribution functions)
+/// and its asymptotic p-value, or `None` if one of the samples is empty.
+pub fn kolmogorov_smirnov(a: &[u64], b: &[u64]) -> Option<(f64, f64)> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+
+    let mut sorted_a = a.to_vec();
+    let mut sorted_b = b.to_vec();
+    sorted_a.sort_unstable();
+    sorted_b.sort_unstable();
+
+    let (mut index_a, mut index_b) = (0, 0);
+    let mut statistic: f64 = 0.0;
+    while index_a < sorted_a.len() && index_b < sorted_b.len() {
+        // step over all equal values in both samples at once
+        let value = u64::min(sorted_a[index_a], sorted_b[index_b]);
+        while index_a < sorted_a.len() && sorted_a[index_a] == value {
+            index_a += 1;
+        }
+        while index_b < sorted_b.len() && sorted_b[index_b] == value {
+            index_b += 1;
+        }
+        let distance = f64::abs(
+            index_a as f64 / sorted_a.len() as f64 - index_b as f64 / sorted_b.len() as f64,
+        );
+        statistic = f64::max(statistic, distance);
+    }
+
+    let p_value =
+        kolmogorov_smirnov_p_value(statistic, sorted_a.len() as f64, sorted_b.len() as f64);
+    Some((statistic, p_value))
+}
+
+/// Returns the asymptotic p-value of the two-sample Kolmogorov-Smirnov `statistic` for samples of the sizes `n_a` and `n_b`.
+fn kolmogorov_smirnov_p_value(statistic: f64, n_a: f64, n_b: f64) -> f64 {
+    let effective_n = f64::sqrt(n_a * n_b / (n_a + n_b));
+    kolmogorov_survival((effective_n + 0.12 + 0.11 / effective_n) * statistic)
+}
+
+/// Returns the survival function of the Kolmogorov distribution at `lambda`.
+fn kolmogorov_survival(lambda: f64) -> f64 {
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let j = j as f64;
+        let term = sign * f64::exp(-2.0 * j * j * lambda * lambda);
+        sum += term;
+        if term.abs() < 1e-12 {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Counts the values of two sample groups (a and b) in bins of equal width, e.g. for distribution tests
+/// over all measurements without retaining them.
+/// Only the non-empty bins are stored, so the memory is bounded by the range of the values divided by the bin width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwoSampleHistogram {
+    bin_width: u64,
+    /// The counts of group a and b by the index of the bin (the value divided by the bin width).
+    bins: BTreeMap<u64, [u64; 2]>,
+    counts: [u64; 2],
+}
+
+impl TwoSampleHistogram {
+    /// Create a new empty histogram with bins of `bin_width` values.
+    /// Panics if `bin_width` is zero.
+    pub fn new(bin_width: u64) -> Self {
+        assert!(bin_width > 0, "the bin width must be larger than zero");
+        Self {
+            bin_width,
+            bins: BTreeMap::new(),
+            counts: [0, 0],
+        }
+    }
+
+    /// Adds a new value to one of the two sample groups (a or b).
+    /// Set `is_sample_group_a` to true, if the value belongs to group a.
+    pub fn push(&mut self, value: u64, is_sample_group_a: bool) {
+        let index = if is_sample_group_a { 0 } else { 1 };
+        self.bins.entry(value / self.bin_width).or_insert([0, 0])[index] += 1;
+        self.counts[index] += 1;
+    }
+
+    /// Returns the width of the bins.
+    pub fn get_bin_width(&self) -> u64 {
+        self.bin_width
+    }
+
+    /// Returns the number of samples for group a and b.
+    pub fn get_number_of_samples(&self) -> [f64; 2] {
+        [self.counts[0] as f64, self.counts[1] as f64]
+    }
+
+    /// Returns the lower bound and the counts of group a and b of all non-empty bins in ascending order.
+    pub fn bins(&self) -> impl Iterator<Item = (u64, [u64; 2])> + '_ {
+        self.bins
+            .iter()
+            .map(move |(index, counts)| (index * self.bin_width, *counts))
+    }
+
+    /// Computes the two-sample Kolmogorov-Smirnov test of the binned values.
+    /// Returns the statistic (the largest distance between the empirical distribution functions at the bin
+    /// boundaries) and its asymptotic p-value, or `None` if one of the groups is empty.
+    /// The binning can only reduce the statistic, so the test is conservative for bins wider than one value.
+    pub fn kolmogorov_smirnov(&self) -> Option<(f64, f64)> {
+        if self.counts[0] == 0 || self.counts[1] == 0 {
+            return None;
+        }
+
+        let [n_a, n_b] = self.get_number_of_samples();
+        let mut cumulative = [0u64, 0u64];
+        let mut statistic: f64 = 0.0;
+        for counts in self.bins.values() {
+            cumulative[0] += counts[0];
+            cumulative[1] += counts[1];
+            let distance = f64::abs(cumulative[0] as f64 / n_a - cumulative[1] as f64 / n_b);
+            statistic = f64::max(statistic, distance);
+        }
+        Some((statistic, kolmogorov_smirnov_p_value(statistic, n_a, n_b)))
+    }
+
+    /// Computes the two-sample Anderson-Darling test of the binned values (Scholz and Stephens, 1987,
+    /// with the correction for ties, i.e. values in the same bin).
+    /// In contrast to the Kolmogorov-Smirnov test, differences in the tails of the distributions are weighted higher.
+    /// Returns the standardized statistic, which is approximately 0 for identical distributions, and its approximate
+    /// p-value, or `None` if one of the groups is empty, there are less than four values, or all values are in the same bin.
+    /// The p-value is interpolated from the table of Scholz and Stephens and is only rough below 0.001.
+    pub fn anderson_darling(&self) -> Option<(f64, f64)> {
+        if self.counts[0] == 0 || self.counts[1] == 0 || self.bins.len() < 2 {
+            return None;
+        }
+        let [n_a, n_b] = self.get_number_of_samples();
+        let n = n_a + n_b;
+        if n < 4.0 {
+            return None;
+        }
+
+        let group_sizes = [n_a, n_b];
+        let mut sums = [0.0, 0.0];
+        let mut cumulative = [0u64, 0u64];
+        for counts in self.bins.values() {
+            let ties = (counts[0] + counts[1]) as f64;
+            // the number of values below the bin plus half of the values in the bin
+            let midrank = (cumulative[0] + cumulative[1]) as f64 + ties / 2.0;
+            let denominator = midrank * (n - midrank) - n * ties / 4.0;
+            if denominator > 0.0 {
+                for (index, sum) in sums.iter_mut().enumerate() {
+                    let midrank_group = cumulative[index] as f64 + counts[index] as f64 / 2.0;
+                    let difference = n * midrank_group - group_sizes[index] * midrank;
+                    *sum += ties / n * difference * difference / denominator;
+                }
+            }
+            cumulative[0] += counts[0];
+            cumulative[1] += counts[1];
+        }
+        let statistic = (n - 1.0) / n * (sums[0] / n_a + sums[1] / n_b);
+
+        // standardize with the mean (k - 1) and the variance of the statistic for k = 2 samples
+        let k = 2.0;
+        let h_sum = 1.0 / n_a + 1.0 / n_b;
+        let (h, g) = anderson_darling_harmonic_sums(self.counts[0] + self.counts[1]);
+        let a = (4.0 * g - 6.0) * (k - 1.0) + (10.0 - 6.0 * g) * h_sum;
+        let b = (2.0 * g - 4.0) * k * k + 8.0 * h * k + (2.0 * g - 14.0 * h - 4.0) * h_sum
+            - 8.0 * h
+            + 4.0 * g
+            - 6.0;
+        let c = (6.0 * h + 2.0 * g - 2.0) * k * k
+            + (4.0 * h - 4.0 * g + 6.0) * k
+            + (2.0 * h - 6.0) * h_sum
+            + 4.0 * h;
+        let d = (2.0 * h + 6.0) * k * k - 4.0 * h * k;
+        let variance = (((a * n + b) * n + c) * n + d) / ((n - 1.0) * (n - 2.0) * (n - 3.0));
+        let standardized = (statistic - (k - 1.0)) / f64::sqrt(variance);
+        Some((standardized, anderson_darling_p_value(standardized)))
+    }
+
+    /// Estimates the mutual information between the group and the bin of a value in bits, i.e. how much is learned
+    /// about the group of a measurement from its binned value (0 for identical and at most 1 for completely
+    /// separated distributions of equally sized groups).
+    /// The plug-in estimate is biased upwards, so the Miller-Madow correction `(bins - 1) / (2 N ln 2)` is subtracted
+    /// and the result is clamped to 0. Returns `None` if one of the groups is empty.
+    pub fn mutual_information(&self) -> Option<f64> {
+        if self.counts[0] == 0 || self.counts[1] == 0 {
+            return None;
+        }
+
+        let [n_a, n_b] = self.get_number_of_samples();
+        let n = n_a + n_b;
+        let group_sizes = [n_a, n_b];
+        let mut information = 0.0;
+        for counts in self.bins.values() {
+            let bin_size = (counts[0] + counts[1]) as f64;
+            for (count, group_size) in counts.iter().zip(group_sizes.iter()) {
+                if *count > 0 {
+                    let count = *count as f64;
+                    // p(group, bin) * ln(p(group, bin) / (p(group) * p(bin)))
+                    information += count / n * (count * n / (group_size * bin_size)).ln();
+                }
+            }
+        }
+        let bias = (self.bins.len() as f64 - 1.0) / (2.0 * n);
+        // convert from nats to bits
+        Some(f64::max(information - bias, 0.0) / core::f64::consts::LN_2)
+    }
+}
+
+/// Returns the sums `h = sum_{i=1}^{n-1} 1/i` and `g = sum_{i=1}^{n-2} sum_{j=i+1}^{n-1} 1/((n-i) j)`
+/// of the variance of the Anderson-Darling statistic for `n` values.
+/// For large `n` their asymptotic values are used, as the error is negligible compared to the variance.
+fn anderson_darling_harmonic_sums(n: u64) -> (f64, f64) {
+    const EXACT_LIMIT: u64 = 1_000_000;
+    const EULER_MASCHERONI: f64 = 0.577_215_664_901_532_9;
+    if n > EXACT_LIMIT {
+        let m = (n - 1) as f64;
+        let h = m.ln() + EULER_MASCHERONI + 1.0 / (2.0 * m);
+        let g = core::f64::consts::PI * core::f64::consts::PI / 6.0;
+        return (h, g);
+    }
+
+    let mut h = 0.0;
+    let mut g = 0.0;
+    // sum_{i=1}^{j-1} 1/(n-i)
+    let mut inner = 0.0;
+    for j in 1..n {
+        g += inner / j as f64;
+        inner += 1.0 / (n - j) as f64;
+        h += 1.0 / j as f64;
+    }
+    (h, g)
+}
+
+/// Returns the approximate p-value of the standardized two-sample Anderson-Darling statistic by interpolating
+/// the logarithm of the significance levels between the critical values of Scholz and Stephens (1987).
+/// Outside of the table the outermost segments are extrapolated.
+fn anderson_darling_p_value(standardized: f64) -> f64 {
+    // critical values b0 + b1 / sqrt(m) + b2 / m for m = k - 1 = 1
+    #[allow(clippy::approx_constant)]
+    const CRITICAL_VALUES: [(f64, f64); 7] = [
+        (0.325, 0.25),
+        (1.226, 0.1),
+        (1.961, 0.05),
+        (2.718, 0.025),
+        (3.752, 0.01),
+        (4.592, 0.005),
+        (6.546, 0.001),
+    ];
+    let segment = CRITICAL_VALUES
+        .windows(2)
+        .position(|window| standardized < window[1].0)
+        .unwrap_or(CRITICAL_VALUES.len() - 2);
+    let (x0, p0) = CRITICAL_VALUES[segment];
+    let (x1, p1) = CRITICAL_VALUES[segment + 1];
+    let ln_p = p0.ln() + (standardized - x0) / (x1 - x0) * (p1.ln() - p0.ln());
+    f64::min(ln_p.exp(), 1.0)
+}
+
+/// Computes the overlapping coefficient of the samples `a` and `b`, i.e. the area of the overlap of their
+/// histograms with `bins` bins of equal width over the common range of both samples.
+/// The result is in [0, 1]: 1 means identical and 0 completely separated distributions.
+/// Returns 0 if one of the samples is empty or `bins` is zero.
+pub fn overlap_coefficient(a: &[u64], b: &[u64], bins: usize) -> f64 {
+    if a.is_empty() || b.is_empty() || bins == 0 {
+        return 0.0;
+    }
+
+    let min = a.iter().chain(b).copied().min().unwrap_or(0);
+    let max = a.iter().chain(b).copied().max().unwrap_or(0);
+    let width = (max - min) as f64 / bins as f64;
+    let histogram = |data: &[u64]| {
+        let mut counts = vec![0.0; bins];
+        for value in data {
+            let bin = if width == 0.0 {
+                0
+            } else {
+                usize::min(((value - min) as f64 / width) as usize, bins - 1)
+            };
+            counts[bin] += 1.0;
+        }
+        counts
+    };
+
+    let histogram_a = histogram(a);
+    let histogram_b = histogram(b);
+    histogram_a
+        .iter()
+        .zip(&histogram_b)
+        .map(|(count_a, count_b)| f64::min(count_a / a.len() as f64, count_b / b.len() as f64))
+        .sum()
+}
+
+/// The number of bits below the most significant bit of a value that select its bin in a [`QuantileSketch`].
+const SKETCH_PRECISION_BITS: u32 = 7;
+
+/// Estimates the quantiles of a stream of integer values with a histogram of logarithmically growing bins
+/// (like HdrHistogram) without storing the values. Values below 256 have a bin of their own, larger values share
+/// bins whose width is at most 1/128 of the values, so the memory only grows with the logarithm of the largest value.
+/// All quantiles are estimated from the same bins, so the estimates are nondecreasing in the quantile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantileSketch {
+    /// The number of values in each bin.
+    counts: Vec<u64>,
+    count: u64,
+}
+
+impl QuantileSketch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new value to the stream.
+    pub fn push(&mut self, value: u64) {
+        let bin = sketch_bin(value);
+        if self.counts.len() <= bin {
+            self.counts.resize(bin + 1, 0);
+        }
+        self.counts[bin] += 1;
+        self.count += 1;
+    }
+
+    /// Returns the number of values that were pushed.
+    pub fn get_number_of_samples(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the estimated value at each quantile of `fractions` at the same position as `percentile_sorted`,
+    /// i.e. the middle of the bin of that value. A quantile outside of the range `[0, 1]` is `None`;
+    /// all quantiles are `None` if no value was pushed.
+    pub fn quantiles(&self, fractions: &[f64]) -> Vec<Option<u64>> {
+        let cumulative_counts: Vec<u64> = self
+            .counts
+            .iter()
+            .scan(0, |cumulative_count, count| {
+                *cumulative_count += count;
+                Some(*cumulative_count)
+            })
+            .collect();
+        fractions
+            .iter()
+            .map(|fraction| {
+                if self.count == 0 || !(0.0..=1.0).contains(fraction) {
+                    return None;
+                }
+                let position = u64::min((self.count as f64 * fraction) as u64, self.count - 1);
+                // the first bin that contains more values than the position
+                let bin = cumulative_counts.partition_point(|count| *count <= position);
+                Some(sketch_bin_value(bin))
+            })
+            .collect()
+    }
+}
+
+/// Returns the bin of `value` in a [`QuantileSketch`].
+fn sketch_bin(value: u64) -> usize {
+    if value < 2 << SKETCH_PRECISION_BITS {
+        return value as usize;
+    }
+    // keep the most significant bit and the precision bits below it
+    let shift = u64::BITS - value.leading_zeros() - (SKETCH_PRECISION_BITS + 1);
+    ((shift as usize) << SKETCH_PRECISION_BITS) + (value >> shift) as usize
+}
+
+/// Returns the value in the middle of `bin` of a [`QuantileSketch`].
+fn sketch_bin_value(bin: usize) -> u64 {
+    if bin < 2 << SKETCH_PRECISION_BITS {
+        return bin as u64;
+    }
+    let shift = (bin >> SKETCH_PRECISION_BITS) - 1;
+    let lower_bound = ((bin - (shift << SKETCH_PRECISION_BITS)) as u64) << shift;
+    lower_bound + (1 << shift) / 2
+}
+
+/// Returns the value at the percentile `which` of the `data`.
+/// The data does not have to be sorted: the value is selected from a copy in linear time.
+/// `which` must be in the range `[0, 1]`, otherwise `None` is returned.
+/// `None` is also returned for empty data.
+pub fn percentile(data: &[u64], which: f64) -> Option<u64> {
+    if data.is_empty() || !(0.0..=1.0).contains(&which) {
+        return None;
+    }
+    let mut values = data.to_vec();
+    // the same position as in `percentile_sorted`
+    let array_position = usize::min((values.len() as f64 * which) as usize, values.len() - 1);
+    let (_, value, _) = values.select_nth_unstable(array_position);
+    Some(*value)
+}
+
+/// Returns the value at the percentile `which` of the already sorted `data` (ascending order).
+/// `which` must be in the range `[0, 1]`, otherwise `None` is returned.
+/// `None` is also returned for empty data.
+pub fn percentile_sorted(sorted_data: &[u64], which: f64) -> Option<u64> {
+    if sorted_data.is_empty() || !(0.0..=1.0).contains(&which) {
+        return None;
+    }
+
+    // which == 1.0 would point behind the last element
+    let array_position = usize::min(
+        (sorted_data.len() as f64 * which) as usize,
+        sorted_data.len() - 1,
+    );
+    Some(sorted_data[array_position])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_of_empty_data_is_none() {
+        assert_eq!(percentile(&[], 0.5), None);
+        assert_eq!(percentile_sorted(&[], 0.5), None);
+    }
+
+    #[test]
+    fn percentile_bounds_are_the_extremes() {
+        let data = [5, 1, 4, 2, 3];
+        assert_eq!(percentile(&data, 0.0), Some(1));
+        assert_eq!(percentile(&data, 1.0), Some(5));
+        assert_eq!(percentile(&data, 0.5), Some(3));
+        assert_eq!(percentile_sorted(&[1, 2, 3, 4, 5], 0.0), Some(1));
+        assert_eq!(percentile_sorted(&[1, 2, 3, 4, 5], 1.0), Some(5));
+    }
+
+    #[test]
+    fn percentile_out_of_range_is_none() {
+        let data = [1, 2, 3];
+        for which in [-0.1, 1.1, f64::NAN, f64::INFINITY] {
+            assert_eq!(percentile(&data, which), None);
+            assert_eq!(percentile_sorted(&data, which), None);
+        }
+    }
+
+    /// Returns pseudo-random values in `[0, 1)` of a xorshift generator.
+    fn uniform_values(seed: u64) -> impl Iterator<Item = f64> {
+        let mut state = seed;
+        core::iter::repeat_with(move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        })
+    }
+
+    #[test]
+    fn windowed_t_only_reflects_the_last_window() {
+        const WINDOW_SIZE: usize = 200;
+
+        let mut windowed = WindowedTTest::new(WINDOW_SIZE);
+        let mut cumulative = TTest::new();
+        let mut values = uniform_values(1);
+        for i in 0..20_000 {
+            let value = values.next().unwrap();
+            windowed.push(value, i % 2 == 0);
+            cumulative.push(value, i % 2 == 0);
+        }
+        let stable_t = f64::abs(cumulative.compute().unwrap());
+        assert!(f64::abs(windowed.compute().unwrap()) < 5.0);
+
+        // the machine gets noisy for group a in the last window
+        for i in 0..2 * WINDOW_SIZE {
+            let is_group_a = i % 2 == 0;
+            let value = values.next().unwrap() + if is_group_a { 1.0 } else { 0.0 };
+            windowed.push(value, is_group_a);
+            cumulative.push(value, is_group_a);
+        }
+        assert_eq!(windowed.get_number_of_samples(), [WINDOW_SIZE, WINDOW_SIZE]);
+        let windowed_t = f64::abs(windowed.compute().unwrap());
+        let cumulative_t = f64::abs(cumulative.compute().unwrap());
+        assert!(windowed_t > 20.0, "windowed t: {}", windowed_t);
+        assert!(
+            cumulative_t < stable_t + 0.5 * windowed_t,
+            "cumulative t: {}, windowed t: {}",
+            cumulative_t,
+            windowed_t
+        );
+    }
+
+    /// Returns `n` pseudo-random execution times around `mean`.
+    fn execution_times(seed: u64, n: usize, mean: u64) -> Vec<u64> {
+        uniform_values(seed)
+            .take(n)
+            .map(|value| mean - 50 + (value * 100.0) as u64)
+            .collect()
+    }
+
+    #[test]
+    fn jackknife_standard_error_shrinks_with_the_sample_size() {
+        let standard_error = |n| {
+            let (effect_size, standard_error) =
+                jackknife_effect_size(&execution_times(1, n, 1010), &execution_times(2, n, 1000));
+            assert!(effect_size > 0.0);
+            standard_error
+        };
+        let small = standard_error(100);
+        let medium = standard_error(1000);
+        let large = standard_error(10_000);
+        assert!(small > medium && medium > large);
+        // the standard error decreases with the square root of the sample size
+        assert!((small / large - 10.0).abs() < 3.0, "{} / {}", small, large);
+        assert!(jackknife_effect_size(&[1], &[1, 2]).0.is_nan());
+    }
+
+    /// Returns true, if `a` and `b` are equal up to a relative error of `1e-9`.
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * f64::max(a.abs(), b.abs())
+    }
+
+    #[test]
+    fn merged_raw_stats_equal_a_single_test() {
+        let mut single = TTest::new();
+        let mut merged = TTest::new();
+        let mut values = uniform_values(3);
+        for part in 0..4 {
+            let mut partial = TTest::new();
+            for i in 0..1000 {
+                let is_group_a = i % 3 != 0;
+                let value = values.next().unwrap() * 100.0 + if is_group_a { 1.0 } else { 0.0 };
+                partial.push(value, is_group_a);
+                single.push(value, is_group_a);
+            }
+            // e.g. transferred from another machine
+            let reconstructed = TTest::from_raw_stats(partial.raw_stats());
+            assert_eq!(reconstructed.raw_stats(), partial.raw_stats());
+            assert_eq!(reconstructed.compute(), partial.compute());
+            if part == 0 {
+                merged = reconstructed;
+            } else {
+                merged.merge(&reconstructed);
+            }
+        }
+        for (merged, single) in merged.raw_stats().iter().zip(single.raw_stats().iter()) {
+            assert!(approx_eq(merged.0, single.0));
+            assert!(approx_eq(merged.1, single.1));
+            assert_eq!(merged.2, single.2);
+        }
+        assert!(approx_eq(
+            merged.compute().unwrap(),
+            single.compute().unwrap()
+        ));
+    }
+
+    #[test]
+    fn an_outlier_inflates_the_welch_t_but_not_the_robust_t() {
+        let mut welch = TTest::new();
+        let mut robust = RobustTTest::new();
+        let mut values = uniform_values(4);
+        for i in 0..2000 {
+            let value = values.next().unwrap() * 100.0;
+            welch.push(value, i % 2 == 0);
+            robust.push(value, i % 2 == 0);
+        }
+        let welch_before = welch.compute().unwrap();
+        let robust_before = robust.compute().unwrap();
+
+        // a single interrupt in group a
+        welch.push(1e6, true);
+        robust.push(1e6, true);
+        let welch_after = welch.compute().unwrap();
+        let robust_after = robust.compute().unwrap();
+        assert!(
+            (welch_after - welch_before).abs() > 0.5,
+            "{} -> {}",
+            welch_before,
+            welch_after
+        );
+        assert!(
+            (robust_after - robust_before).abs() < 0.1,
+            "{} -> {}",
+            robust_before,
+            robust_after
+        );
+    }
+
+    #[test]
+    fn grubbs_statistic_is_high_for_an_injected_extreme() {
+        let mut test = TTest::new();
+        let mut values = uniform_values(5);
+        for i in 0..2000 {
+            test.push(values.next().unwrap() * 100.0, i % 2 == 0);
+        }
+        // the critical value for 1000 samples is about 3.9 at a significance level of 0.05
+        let [before_a, before_b] = test.grubbs_statistic();
+        assert!(before_a.unwrap() < 3.0 && before_b.unwrap() < 3.0);
+
+        test.push(1000.0, true);
+        let [grubbs_a, grubbs_b] = test.grubbs_statistic();
+        assert!(grubbs_a.unwrap() > 10.0, "{:?}", grubbs_a);
+        assert_eq!(grubbs_b, before_b);
+        assert_eq!(TTest::new().grubbs_statistic(), [None, None]);
+    }
+
+    #[test]
+    fn reservoir_is_capped_and_approximately_uniform() {
+        const SIZE: usize = 1000;
+        const VALUES: u64 = 100_000;
+
+        let mut reservoir = Reservoir::new(SIZE);
+        for value in 0..VALUES {
+            reservoir.push(value, true);
+        }
+        for value in 0..SIZE as u64 / 2 {
+            reservoir.push(value, false);
+        }
+        let (a, b) = reservoir.get_samples();
+        assert_eq!(a.len(), SIZE);
+        assert_eq!(b.len(), SIZE / 2);
+
+        // each tenth of the values is expected 100 times with a standard deviation of about 9.5
+        let mut deciles = [0; 10];
+        for value in a {
+            deciles[(value * 10 / VALUES) as usize] += 1;
+        }
+        assert!(
+            deciles.iter().all(|count| (50..150).contains(count)),
+            "{:?}",
+            deciles
+        );
+    }
+
+    #[test]
+    fn combined_p_value_is_small_if_several_tests_show_signal() {
+        assert_eq!(fisher_combined_p_value(&[]), None);
+        // a single test is not changed
+        assert!(approx_eq(fisher_combined_p_value(&[0.2]).unwrap(), 0.2));
+        // chi-square of 29.96 with 10 degrees of freedom
+        let combined = fisher_combined_p_value(&[0.05; 5]).unwrap();
+        assert!((combined - 0.00087).abs() < 0.00002, "{}", combined);
+        assert!(fisher_combined_p_value(&[0.5; 5]).unwrap() > 0.5);
+
+        // tests of independent values with a small difference, which is at most weakly significant in each test
+        let p_values: Vec<f64> = (0..4)
+            .map(|seed| {
+                let mut test = TTest::new();
+                let mut values = uniform_values(10 + seed);
+                for i in 0..400 {
+                    let is_group_a = i % 2 == 0;
+                    let value = values.next().unwrap() + if is_group_a { 0.08 } else { 0.0 };
+                    test.push(value, is_group_a);
+                }
+                test.p_value().unwrap()
+            })
+            .collect();
+        let combined = fisher_combined_p_value(&p_values).unwrap();
+        assert!(
+            p_values.iter().all(|p| combined < *p) && combined < 0.01,
+            "{:?} -> {}",
+            p_values,
+            combined
+        );
+    }
+
+    #[test]
+    fn m2_is_the_sum_of_squared_deviations() {
+        let a = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let b = [1.0, 3.0];
+        let mut test = TTest::new();
+        for value in a {
+            test.push(value, true);
+        }
+        for value in b {
+            test.push(value, false);
+        }
+        // the mean of a is 5 and the mean of b is 2
+        assert_eq!(test.get_mean(), [5.0, 2.0]);
+        assert_eq!(test.get_m2(), [32.0, 2.0]);
+        assert_eq!(test.get_number_of_samples(), [8.0, 2.0]);
+
+        let values: Vec<f64> = uniform_values(6)
+            .take(1000)
+            .map(|value| value * 1000.0)
+            .collect();
+        let mut test = TTest::new();
+        for value in &values {
+            test.push(*value, true);
+        }
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let m2 = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>();
+        assert!(approx_eq(test.get_mean()[0], mean));
+        assert!(approx_eq(test.get_m2()[0], m2));
+        assert_eq!(test.get_m2()[1], 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn t_test_survives_a_json_round_trip() {
+        let mut values = uniform_values(7);
+        for (test, number_of_values) in [
+            (TTest::new(), 0),
+            (TTest::new(), 1),
+            (TTest::new(), 1000),
+            (TTest::new().with_compensated_summation(), 1000),
+        ] {
+            let mut test = test;
+            for i in 0..number_of_values {
+                test.push(values.next().unwrap() * 100.0, i % 3 == 0);
+            }
+            let json = serde_json::to_string(&test).unwrap();
+            let restored: TTest = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, test);
+            assert_eq!(restored.raw_stats(), test.raw_stats());
+            assert_eq!(restored.compute(), test.compute());
+        }
+    }
+
+    #[test]
+    fn overlap_coefficient_separates_distributions() {
+        let a = execution_times(8, 10_000, 1000);
+        let b = execution_times(9, 10_000, 1000);
+        let identical = overlap_coefficient(&a, &a, 20);
+        assert!(approx_eq(identical, 1.0), "{}", identical);
+        // samples of the same distribution only differ by the sampling noise
+        let same = overlap_coefficient(&a, &b, 20);
+        assert!(same > 0.95, "{}", same);
+
+        let partially_shifted = overlap_coefficient(&a, &execution_times(9, 10_000, 1050), 20);
+        assert!(
+            (partially_shifted - 0.5).abs() < 0.1,
+            "{}",
+            partially_shifted
+        );
+        let separated = overlap_coefficient(&a, &execution_times(9, 10_000, 2000), 20);
+        assert_eq!(separated, 0.0);
+
+        assert_eq!(overlap_coefficient(&a, &[], 20), 0.0);
+        assert_eq!(overlap_coefficient(&[5; 10], &[5; 3], 20), 1.0);
+    }
+
+    #[test]
+    fn compensated_summation_is_closer_to_the_exact_statistics() {
+        // the values OFFSET + 0, ..., OFFSET + 9 repeated, which are exact in f64
+        const OFFSET: f64 = 1e9;
+        const REPETITIONS: usize = 200_000;
+
+        let mut plain = TTest::new();
+        let mut compensated = TTest::new().with_compensated_summation();
+        for _ in 0..REPETITIONS {
+            for i in 0..10 {
+                plain.push(OFFSET + i as f64, true);
+                compensated.push(OFFSET + i as f64, true);
+            }
+        }
+        let n = (10 * REPETITIONS) as f64;
+        let exact_mean = OFFSET + 4.5;
+        // the variance of 0, ..., 9 is 8.25
+        let exact_m2 = 8.25 * n;
+        let plain_errors = (
+            (plain.get_mean()[0] - exact_mean).abs(),
+            (plain.get_m2()[0] - exact_m2).abs(),
+        );
+        let compensated_errors = (
+            (compensated.get_mean()[0] - exact_mean).abs(),
+            (compensated.get_m2()[0] - exact_m2).abs(),
+        );
+        assert!(
+            compensated_errors.0 < plain_errors.0 && compensated_errors.1 < plain_errors.1,
+            "plain: {:?}, compensated: {:?}",
+            plain_errors,
+            compensated_errors
+        );
+        assert!(approx_eq(compensated.get_m2()[0], exact_m2));
+    }
+
+    #[test]
+    fn quantile_sketch_is_exact_for_small_values_and_close_for_large_ones() {
+        let fractions: Vec<f64> = (0..=100).map(|i| i as f64 / 100.0).collect();
+        assert!(QuantileSketch::new()
+            .quantiles(&fractions)
+            .iter()
+            .all(Option::is_none));
+
+        for scale in [1.0, 255.0, 1e6, 1e15] {
+            let mut values: Vec<u64> = uniform_values(7)
+                .take(10_000)
+                .map(|value| (value * value * scale) as u64)
+                .collect();
+            let mut sketch = QuantileSketch::new();
+            values.iter().for_each(|value| sketch.push(*value));
+            assert_eq!(sketch.get_number_of_samples(), 10_000);
+            values.sort_unstable();
+
+            let estimates: Vec<u64> = sketch.quantiles(&fractions).into_iter().flatten().collect();
+            assert_eq!(estimates.len(), fractions.len());
+            // the estimates of all quantiles come from the same bins, so they are ascending
+            assert!(estimates.windows(2).all(|pair| pair[0] <= pair[1]));
+            for (fraction, estimate) in fractions.iter().zip(estimates) {
+                let exact = percentile_sorted(&values, *fraction).unwrap();
+                if exact < 256 {
+                    assert_eq!(estimate, exact);
+                } else {
+                    let relative_error = (estimate as f64 - exact as f64).abs() / exact as f64;
+                    assert!(relative_error <= 1.0 / 256.0, "{} {}", estimate, exact);
+                }
+            }
+        }
+        let mut sketch = QuantileSketch::new();
+        sketch.push(u64::MAX);
+        assert_eq!(
+            sketch.quantiles(&[0.5, 1.5]),
+            vec![Some(u64::MAX - (1 << 55) + 1), None]
+        );
+    }
+
+    #[test]
+    fn mann_whitney_u_test_matches_known_values() {
+        // reference: scipy.stats.mannwhitneyu(a, b, use_continuity=False, method="asymptotic")
+        let mut test = MannWhitneyUTest::new();
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            test.push(value, true);
+        }
+        for value in [2.0, 3.0, 4.0, 5.0, 6.0, 7.0] {
+            test.push(value, false);
+        }
+        assert_eq!(test.u_statistic(), Some(8.0));
+        // the 4 ties of two values reduce the variance from 30 to 29.4545
+        assert!((test.compute().unwrap() + 1.2898).abs() < 1e-4);
+        assert!((test.p_value().unwrap() - 0.1971).abs() < 1e-4);
+    }
+
+    #[test]
+    fn anderson_darling_matches_known_values() {
+        // reference: the midrank statistic of scipy.stats.anderson_ksamp([a, b]),
+        // standardized with the variance of Scholz and Stephens (1987)
+        let mut histogram = TwoSampleHistogram::new(1);
+        for value in 1..=8 {
+            histogram.push(value, true);
+        }
+        for value in 5..=12 {
+            histogram.push(value, false);
+        }
+        let (statistic, p_value) = histogram.anderson_darling().unwrap();
+        assert!((statistic - 3.9662).abs() < 1e-4);
+        // between the critical values 3.752 (p = 0.01) and 4.592 (p = 0.005)
+        assert!(p_value > 0.005 && p_value < 0.01);
+    }
+
+    #[test]
+    fn mutual_information_matches_known_values() {
+        // completely separated groups: ln 2 nats minus the Miller-Madow correction 1 / (2 * 16)
+        let mut histogram = TwoSampleHistogram::new(1);
+        for _ in 0..8 {
+            histogram.push(1, true);
+            histogram.push(5, false);
+        }
+        let expected = (core::f64::consts::LN_2 - 1.0 / 32.0) / core::f64::consts::LN_2;
+        assert!((histogram.mutual_information().unwrap() - expected).abs() < 1e-12);
+
+        // partially overlapping groups, computed from the plug-in estimate with 4 bins and 12 values
+        let mut histogram = TwoSampleHistogram::new(1);
+        for value in [1, 1, 1, 2, 2, 3] {
+            histogram.push(value, true);
+        }
+        for value in [2, 3, 3, 3, 4, 4] {
+            histogram.push(value, false);
+        }
+        assert!((histogram.mutual_information().unwrap() - 0.319_663).abs() < 1e-6);
+
+        // identical groups are clamped to 0
+        let mut histogram = TwoSampleHistogram::new(1);
+        for value in [1, 2, 3] {
+            histogram.push(value, true);
+            histogram.push(value, false);
+        }
+        assert_eq!(histogram.mutual_information(), Some(0.0));
+    }
+
+    #[test]
+    fn welch_t_test_and_p_values_match_known_values() {
+        // reference: scipy.stats.ttest_ind(a, b, equal_var=False)
+        let mut test = TTest::new();
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            test.push(value, true);
+        }
+        for value in [2.0, 3.0, 4.0, 5.0, 6.0, 7.0] {
+            test.push(value, false);
+        }
+        assert!((test.compute().unwrap() + 1.4412).abs() < 1e-4);
+        assert!((test.degrees_of_freedom().unwrap() - 8.9894).abs() < 1e-4);
+        assert!((test.p_value().unwrap() - 0.1835).abs() < 1e-4);
+
+        // I_0.4(2, 3) = sum_{j=2}^{4} C(4, j) 0.4^j 0.6^(4-j)
+        assert!((regularized_incomplete_beta(2.0, 3.0, 0.4) - 0.5248).abs() < 1e-12);
+        // the two-sided 5% critical value of the t-distribution with 10 degrees of freedom
+        assert!((student_t_two_sided_p_value(2.228_139, 10.0) - 0.05).abs() < 1e-6);
+        assert!((student_t_two_sided_quantile(0.05, 10.0) - 2.228_139).abs() < 1e-6);
+        assert!((normal_two_sided_p_value(1.959_964) - 0.05).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,813 @@
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64"
+))]
+use core::arch::asm;
+#[cfg(feature = "instant-timer")]
+use std::time::Instant;
+
+use alloc::boxed::Box;
+
+/// A source of timestamps for measuring the execution times, e.g. a CPU cycle counter, a PMU counter,
+/// an OS clock, or a counter provided by a simulator.
+/// The execution time of a computation is the difference of the timestamps before and after it.
+pub trait CycleCounter {
+    /// Returns the current timestamp.
+    fn ticks(&mut self) -> u64;
+    /// Returns the name of the counter, which is recorded in the reports.
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+}
+
+/// Reads the time stamp counter of x86 CPUs with `rdtsc` (see [`cpu_ticks`]), as the original dudect.
+#[cfg(target_arch = "x86_64")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rdtsc;
+
+#[cfg(target_arch = "x86_64")]
+impl CycleCounter for Rdtsc {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        cpu_ticks()
+    }
+
+    fn name(&self) -> &'static str {
+        "rdtsc"
+    }
+}
+
+/// Reads the time stamp counter of x86 CPUs serialized with `rdtscp` and `lfence`, as recommended by Intel.
+/// `rdtscp` waits until all previous instructions (the computation) have executed and the following `lfence`
+/// prevents the next instructions from starting before the counter is read, so the out-of-order execution
+/// can not smear the boundaries between the computations.
+/// This reduces the variance of short computations, but adds a constant overhead of some dozen cycles
+/// to each measurement compared to [`Rdtsc`].
+#[cfg(target_arch = "x86_64")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SerializedRdtsc;
+
+#[cfg(target_arch = "x86_64")]
+impl CycleCounter for SerializedRdtsc {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        serialized_cpu_ticks_end()
+    }
+
+    fn name(&self) -> &'static str {
+        "rdtscp"
+    }
+}
+
+/// Reads the virtual count of the generic timer of AArch64 CPUs (`CNTVCT_EL0`), see [`cpu_ticks`].
+/// The generic timer is always accessible from user space, but it runs at a fixed frequency
+/// (`CNTFRQ_EL0`, often 24 MHz to 1 GHz), which is usually much lower than the CPU frequency.
+#[cfg(target_arch = "aarch64")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GenericTimer;
+
+#[cfg(target_arch = "aarch64")]
+impl CycleCounter for GenericTimer {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        cpu_ticks()
+    }
+
+    fn name(&self) -> &'static str {
+        "cntvct_el0"
+    }
+}
+
+/// Reads the cycle counter of the performance monitors of AArch64 CPUs (`PMCCNTR_EL0`).
+/// It counts CPU cycles, but the kernel must enable the access from user space (`PMUSERENR_EL0`),
+/// e.g. with a kernel module, otherwise reading it raises an illegal instruction exception.
+#[cfg(target_arch = "aarch64")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PmuCycleCounter;
+
+#[cfg(target_arch = "aarch64")]
+impl CycleCounter for PmuCycleCounter {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        let ticks: u64;
+        unsafe {
+            // the isb prevents reading the counter before the preceding instructions are completed
+            asm!("isb", "mrs {}, pmccntr_el0", out(reg) ticks);
+        }
+        ticks
+    }
+
+    fn name(&self) -> &'static str {
+        "pmccntr_el0"
+    }
+}
+
+/// Reads the cycle counter of RISC-V CPUs with `rdcycle`.
+/// Since Linux 6.6 the access from user space is disabled by default and reading it raises an illegal
+/// instruction exception, unless `/proc/sys/kernel/perf_user_access` is set to 2 (see [`rdcycle_accessible`]).
+/// The default cycle counter falls back to [`RdTime`] in this case.
+#[cfg(target_arch = "riscv64")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RdCycle;
+
+#[cfg(target_arch = "riscv64")]
+impl CycleCounter for RdCycle {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        let ticks: u64;
+        unsafe {
+            asm!("rdcycle {}", out(reg) ticks);
+        }
+        ticks
+    }
+
+    fn name(&self) -> &'static str {
+        "rdcycle"
+    }
+}
+
+/// Reads the real-time counter of RISC-V CPUs with `rdtime`, see [`cpu_ticks`].
+/// It is always accessible from user space, but runs at a fixed frequency (often 1 MHz to 25 MHz),
+/// which is much lower than the CPU frequency.
+#[cfg(target_arch = "riscv64")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RdTime;
+
+#[cfg(target_arch = "riscv64")]
+impl CycleCounter for RdTime {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        cpu_ticks()
+    }
+
+    fn name(&self) -> &'static str {
+        "rdtime"
+    }
+}
+
+/// Returns true, if `rdcycle` can be executed in user space.
+/// On Linux the setting `/proc/sys/kernel/perf_user_access` must be 2 (legacy access),
+/// kernels without the setting always allow the access.
+#[cfg(all(feature = "std", target_arch = "riscv64"))]
+pub fn rdcycle_accessible() -> bool {
+    #[cfg(target_os = "linux")]
+    return match std::fs::read_to_string("/proc/sys/kernel/perf_user_access") {
+        Ok(setting) => setting.trim() == "2",
+        Err(_) => true,
+    };
+    #[cfg(not(target_os = "linux"))]
+    return false;
+}
+
+/// Reads the cycle counter of the Data Watchpoint and Trace unit of Cortex-M CPUs (`DWT_CYCCNT`).
+/// The counter is only 32 bits wide and wraps around after 2^32 cycles (about 43 s at 100 MHz),
+/// so the wrap arounds are counted to extend it to 64 bits. This requires that the counter is read
+/// at least once per wrap around, which is the case for the measurements of a dudect test.
+/// The DWT cycle counter is not available on ARMv6-M (Cortex-M0/M0+).
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+#[derive(Debug, Clone, Copy)]
+pub struct DwtCycleCounter {
+    last: u32,
+    wraps: u64,
+}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+impl DwtCycleCounter {
+    const DEMCR: *mut u32 = 0xE000_EDFC as *mut u32;
+    const DWT_CTRL: *mut u32 = 0xE000_1000 as *mut u32;
+    const DWT_CYCCNT: *mut u32 = 0xE000_1004 as *mut u32;
+    const DWT_LAR: *mut u32 = 0xE000_1FB0 as *mut u32;
+
+    /// Enables the trace unit (`DEMCR.TRCENA`) and the cycle counter (`DWT_CTRL.CYCCNTENA`)
+    /// and resets the counter to 0.
+    ///
+    /// # Safety
+    ///
+    /// Must be executed in privileged mode and the DWT must not be used by anything else, e.g. a debugger,
+    /// as its configuration is changed.
+    pub unsafe fn new() -> Self {
+        const TRCENA: u32 = 1 << 24;
+        const CYCCNTENA: u32 = 1;
+        const LAR_UNLOCK: u32 = 0xC5AC_CE55;
+
+        core::ptr::write_volatile(Self::DEMCR, core::ptr::read_volatile(Self::DEMCR) | TRCENA);
+        // the DWT of the Cortex-M7 is locked after reset, the write is ignored by the other cores
+        core::ptr::write_volatile(Self::DWT_LAR, LAR_UNLOCK);
+        core::ptr::write_volatile(Self::DWT_CYCCNT, 0);
+        core::ptr::write_volatile(
+            Self::DWT_CTRL,
+            core::ptr::read_volatile(Self::DWT_CTRL) | CYCCNTENA,
+        );
+        Self { last: 0, wraps: 0 }
+    }
+}
+
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+impl CycleCounter for DwtCycleCounter {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        let count = unsafe { core::ptr::read_volatile(Self::DWT_CYCCNT) };
+        if count < self.last {
+            self.wraps += 1;
+        }
+        self.last = count;
+        self.wraps << 32 | count as u64
+    }
+
+    fn name(&self) -> &'static str {
+        "dwt_cyccnt"
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[link(wasm_import_module = "dudect")]
+extern "C" {
+    fn performance_now() -> f64;
+}
+
+/// Reads the high-resolution time of the browser or of Node.js with `performance.now()`.
+/// The function must be provided by the JavaScript host as the import `dudect.performance_now`,
+/// e.g. with `{ dudect: { performance_now: () => performance.now() } }` in the import object.
+/// The timestamps are nanoseconds, but browsers reduce the resolution to 5 µs to 100 µs,
+/// so each execution time must cover many computations (see `MeasurementContext::with_auto_computations_per_measurement`).
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerformanceNow;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl CycleCounter for PerformanceNow {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        (unsafe { performance_now() } * 1e6) as u64
+    }
+
+    fn name(&self) -> &'static str {
+        "performance.now"
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "wasi"))]
+#[link(wasm_import_module = "wasi_snapshot_preview1")]
+extern "C" {
+    fn clock_time_get(id: u32, precision: u64, time: *mut u64) -> u16;
+}
+
+/// Reads the monotonic clock of the WASI host (`clock_time_get` with `CLOCK_MONOTONIC`) in nanoseconds.
+/// The resolution depends on the runtime, which might reduce it to mitigate timing attacks.
+#[cfg(all(target_arch = "wasm32", target_os = "wasi"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasiClock;
+
+#[cfg(all(target_arch = "wasm32", target_os = "wasi"))]
+impl CycleCounter for WasiClock {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        cpu_ticks()
+    }
+
+    fn name(&self) -> &'static str {
+        "clock_time_get"
+    }
+}
+
+/// Measures the nanoseconds of the monotonic clock of the operating system with [`Instant`]
+/// (`CLOCK_MONOTONIC` on Linux). This works on every target with `std`, but the resolution is much lower
+/// than the one of a cycle counter and reading the clock is slower, so it is only a fallback.
+/// It is the default on architectures without a cycle counter backend.
+#[cfg(feature = "instant-timer")]
+#[derive(Debug, Clone, Copy)]
+pub struct InstantCounter {
+    epoch: Instant,
+}
+
+#[cfg(feature = "instant-timer")]
+impl InstantCounter {
+    /// Create a new counter, whose timestamps are the nanoseconds since its creation.
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "instant-timer")]
+impl Default for InstantCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "instant-timer")]
+impl CycleCounter for InstantCounter {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        self.epoch.elapsed().as_nanos() as u64
+    }
+
+    fn name(&self) -> &'static str {
+        "instant"
+    }
+}
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetCurrentThread() -> *mut core::ffi::c_void;
+    fn QueryThreadCycleTime(thread: *mut core::ffi::c_void, cycle_time: *mut u64) -> i32;
+    fn QueryPerformanceCounter(performance_count: *mut i64) -> i32;
+}
+
+/// Counts the CPU cycles that are charged to the current thread with `QueryThreadCycleTime` on Windows.
+/// Cycles of other threads that preempt the measuring thread are not counted, which reduces the noise
+/// on busy machines, but the counter is only updated by the scheduler and includes some kernel time.
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadCycleTime;
+
+#[cfg(windows)]
+impl CycleCounter for ThreadCycleTime {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        let mut cycle_time = 0;
+        // the pseudo handle of the current thread does not need to be closed
+        let succeeded = unsafe { QueryThreadCycleTime(GetCurrentThread(), &mut cycle_time) };
+        debug_assert_ne!(succeeded, 0, "QueryThreadCycleTime failed");
+        cycle_time
+    }
+
+    fn name(&self) -> &'static str {
+        "QueryThreadCycleTime"
+    }
+}
+
+/// Reads the high-resolution performance counter of Windows with `QueryPerformanceCounter`.
+/// It has a fixed frequency (usually 10 MHz) and counts the wall-clock time, including preemptions.
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerformanceCounter;
+
+#[cfg(windows)]
+impl CycleCounter for PerformanceCounter {
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        let mut performance_count = 0;
+        // it never fails on Windows XP and later
+        let succeeded = unsafe { QueryPerformanceCounter(&mut performance_count) };
+        debug_assert_ne!(succeeded, 0, "QueryPerformanceCounter failed");
+        performance_count as u64
+    }
+
+    fn name(&self) -> &'static str {
+        "QueryPerformanceCounter"
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+extern "C" {
+    fn syscall(number: i64, ...) -> i64;
+    fn read(fd: i32, buf: *mut core::ffi::c_void, count: usize) -> isize;
+    fn close(fd: i32) -> i32;
+}
+
+/// The number of the system call `perf_event_open` (`arch/x86/entry/syscalls/syscall_64.tbl` of Linux).
+#[cfg(all(feature = "std", target_os = "linux", target_arch = "x86_64"))]
+const SYS_PERF_EVENT_OPEN: i64 = 298;
+
+/// The number of the system call `perf_event_open` (`include/uapi/asm-generic/unistd.h` of Linux).
+#[cfg(all(feature = "std", target_os = "linux", target_arch = "aarch64"))]
+const SYS_PERF_EVENT_OPEN: i64 = 241;
+
+/// The attributes of a perf event (`struct perf_event_attr` of `linux/perf_event.h`, version 5).
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+#[repr(C)]
+#[derive(Default)]
+struct PerfEventAttr {
+    event_type: u32,
+    size: u32,
+    config: u64,
+    sample_period: u64,
+    sample_type: u64,
+    read_format: u64,
+    flags: u64,
+    wakeup_events: u32,
+    bp_type: u32,
+    config1: u64,
+    config2: u64,
+    branch_sample_type: u64,
+    sample_regs_user: u64,
+    sample_stack_user: u32,
+    clockid: i32,
+    sample_regs_intr: u64,
+    aux_watermark: u32,
+    sample_max_stack: u16,
+    reserved: u16,
+}
+
+/// Counts hardware events of the current thread with a counter opened by `perf_event_open` on Linux.
+/// Only the events of the measuring thread in user space are counted, so cycles spent in other tasks
+/// and in the kernel are excluded. The counter is read with a system call, which is slower than reading
+/// a cycle counter directly, but works if the direct access (`rdpmc`, `rdtsc`) is restricted.
+/// Opening the counter requires a `perf_event_paranoid` setting of 2 or lower.
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+#[derive(Debug)]
+pub struct PerfEventCounter {
+    fd: i32,
+    name: &'static str,
+}
+
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+impl PerfEventCounter {
+    /// Opens a counter of the CPU cycles (`PERF_COUNT_HW_CPU_CYCLES`) of the current thread.
+    pub fn cpu_cycles() -> std::io::Result<Self> {
+        const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+        Self::open(PERF_COUNT_HW_CPU_CYCLES, "perf_event cycles")
+    }
+
+    /// Opens a counter of the retired instructions (`PERF_COUNT_HW_INSTRUCTIONS`) of the current thread.
+    /// Used as the cycle counter of a context, all tests are applied to the instruction counts instead of
+    /// the cycles. They are not affected by frequency scaling or cache misses, so they are much more stable
+    /// for detecting secret-dependent control flow, but can not detect data-dependent instruction timings.
+    pub fn instructions() -> std::io::Result<Self> {
+        const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+        Self::open(PERF_COUNT_HW_INSTRUCTIONS, "perf_event instructions")
+    }
+
+    /// Opens a counter of the hardware event `config` of the type `PERF_TYPE_HARDWARE` for the current thread.
+    fn open(config: u64, name: &'static str) -> std::io::Result<Self> {
+        const PERF_TYPE_HARDWARE: u32 = 0;
+        const EXCLUDE_KERNEL: u64 = 1 << 5;
+        const EXCLUDE_HV: u64 = 1 << 6;
+        const PERF_FLAG_FD_CLOEXEC: i64 = 1 << 3;
+
+        let attr = PerfEventAttr {
+            event_type: PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config,
+            flags: EXCLUDE_KERNEL | EXCLUDE_HV,
+            ..PerfEventAttr::default()
+        };
+        // measure the calling thread (pid 0) on any CPU (-1) without a group (-1)
+        let fd = unsafe {
+            syscall(
+                SYS_PERF_EVENT_OPEN,
+                &attr as *const PerfEventAttr,
+                0i32,
+                -1i32,
+                -1i32,
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let counter = Self {
+            fd: fd as i32,
+            name,
+        };
+        // the counter is read once, so `ticks` can rely on it being readable
+        counter.read_count()?;
+        Ok(counter)
+    }
+
+    /// Reads the current count of the counter.
+    #[inline(always)]
+    fn read_count(&self) -> std::io::Result<u64> {
+        let mut count: u64 = 0;
+        let length = std::mem::size_of::<u64>();
+        let read_length = unsafe {
+            read(
+                self.fd,
+                &mut count as *mut u64 as *mut core::ffi::c_void,
+                length,
+            )
+        };
+        if read_length < 0 {
+            Err(std::io::Error::last_os_error())
+        } else if read_length != length as isize {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "short read of the perf event counter",
+            ))
+        } else {
+            Ok(count)
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+impl CycleCounter for PerfEventCounter {
+    /// Returns the current count.
+    /// Panics if the counter can not be read anymore, which was checked when it was opened.
+    #[inline(always)]
+    fn ticks(&mut self) -> u64 {
+        match self.read_count() {
+            Ok(count) => count,
+            Err(error) => panic!("the perf event counter can not be read: {}", error),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+impl Drop for PerfEventCounter {
+    fn drop(&mut self) {
+        unsafe {
+            close(self.fd);
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi")),
+        feature = "instant-timer"
+    ))
+))]
+compile_error!(
+    "there is no cycle counter for this architecture, enable the `instant-timer` feature"
+);
+
+/// Stands in for the default cycle counter on `no_std` targets without a cycle counter backend.
+#[cfg(not(any(
+    feature = "std",
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+)))]
+struct MissingCycleCounter;
+
+#[cfg(not(any(
+    feature = "std",
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+)))]
+impl CycleCounter for MissingCycleCounter {
+    fn ticks(&mut self) -> u64 {
+        panic!("there is no default cycle counter for this target, set one with `MeasurementContext::with_cycle_counter`")
+    }
+
+    fn name(&self) -> &'static str {
+        "missing"
+    }
+}
+
+/// Returns the cycle counter that is used by default on the target architecture.
+pub(crate) fn default_cycle_counter() -> Box<dyn CycleCounter> {
+    #[cfg(target_arch = "x86_64")]
+    return Box::new(Rdtsc);
+    #[cfg(target_arch = "aarch64")]
+    return Box::new(GenericTimer);
+    #[cfg(all(feature = "std", target_arch = "riscv64"))]
+    return if rdcycle_accessible() {
+        Box::new(RdCycle)
+    } else {
+        Box::new(RdTime)
+    };
+    #[cfg(all(not(feature = "std"), target_arch = "riscv64"))]
+    return Box::new(RdTime);
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    return Box::new(PerformanceNow);
+    #[cfg(all(target_arch = "wasm32", target_os = "wasi"))]
+    return Box::new(WasiClock);
+    #[cfg(all(
+        feature = "std",
+        not(any(
+            target_arch = "x86_64",
+            target_arch = "aarch64",
+            target_arch = "riscv64",
+            all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+        ))
+    ))]
+    return Box::new(InstantCounter::new());
+    #[cfg(not(any(
+        feature = "std",
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+    )))]
+    return Box::new(MissingCycleCounter);
+}
+
+/// Returns the current CPU ticks count. From the dudect implementation:
+/// Intel actually recommends calling CPUID to serialize the execution flow
+/// and reduce variance in measurement due to out-of-order execution.
+/// We don't do that here, but the serialized measurement is available with [`SerializedRdtsc`].
+/// see §3.2.1 http://www.intel.com/content/www/us/en/embedded/training/ia-32-ia-64-benchmark-code-execution-paper.html
+#[cfg(target_arch = "x86_64")]
+pub fn cpu_ticks() -> u64 {
+    let upper: u64;
+    let lower: u64;
+    unsafe {
+        asm!("rdtsc", out("rax") lower, out("rdx") upper);
+    }
+    upper << 32 | lower
+}
+
+/// Returns the current count of the generic timer (`CNTVCT_EL0`).
+/// The `isb` prevents reading the counter before the preceding instructions are completed.
+#[cfg(target_arch = "aarch64")]
+pub fn cpu_ticks() -> u64 {
+    let ticks: u64;
+    unsafe {
+        asm!("isb", "mrs {}, cntvct_el0", out(reg) ticks);
+    }
+    ticks
+}
+
+/// Returns the current CPU ticks count after all previous instructions have completed locally.
+/// The `lfence` prevents the `rdtsc` from being executed before preceding instructions.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+pub(crate) fn serialized_cpu_ticks_begin() -> u64 {
+    let upper: u64;
+    let lower: u64;
+    unsafe {
+        asm!("lfence", "rdtsc", out("rax") lower, out("rdx") upper);
+    }
+    upper << 32 | lower
+}
+
+/// Returns the current CPU ticks count with `rdtscp`, which waits for all previous instructions to execute.
+/// The following `lfence` prevents subsequent instructions from being executed before the timestamp is read.
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn serialized_cpu_ticks_end() -> u64 {
+    let upper: u64;
+    let lower: u64;
+    unsafe {
+        asm!("rdtscp", "lfence", out("rax") lower, out("rdx") upper, out("rcx") _);
+    }
+    upper << 32 | lower
+}
+
+/// Returns the current count of the generic timer after all previous instructions have completed.
+#[cfg(all(feature = "std", target_arch = "aarch64"))]
+pub(crate) fn serialized_cpu_ticks_begin() -> u64 {
+    cpu_ticks()
+}
+
+/// Returns the current count of the generic timer after all previous instructions have completed.
+/// The trailing `isb` prevents subsequent instructions from being executed before the counter is read.
+#[cfg(target_arch = "aarch64")]
+pub(crate) fn serialized_cpu_ticks_end() -> u64 {
+    let ticks: u64;
+    unsafe {
+        asm!("isb", "mrs {}, cntvct_el0", "isb", out(reg) ticks);
+    }
+    ticks
+}
+
+/// Returns the current count of the real-time counter (`rdtime`).
+/// It is used instead of `rdcycle`, as it is accessible from user space on every system.
+#[cfg(target_arch = "riscv64")]
+pub fn cpu_ticks() -> u64 {
+    let ticks: u64;
+    unsafe {
+        asm!("rdtime {}", out(reg) ticks);
+    }
+    ticks
+}
+
+/// Returns the current count of the real-time counter after all previous memory accesses have completed.
+#[cfg(all(feature = "std", target_arch = "riscv64"))]
+pub(crate) fn serialized_cpu_ticks_begin() -> u64 {
+    let ticks: u64;
+    unsafe {
+        asm!("fence", "rdtime {}", out(reg) ticks);
+    }
+    ticks
+}
+
+/// Returns the current count of the real-time counter after all previous memory accesses have completed.
+#[cfg(all(feature = "std", target_arch = "riscv64"))]
+pub(crate) fn serialized_cpu_ticks_end() -> u64 {
+    serialized_cpu_ticks_begin()
+}
+
+/// Returns the current time of `performance.now()` in nanoseconds, see [`PerformanceNow`].
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn cpu_ticks() -> u64 {
+    PerformanceNow.ticks()
+}
+
+/// Returns the current time of the monotonic clock of the WASI host in nanoseconds.
+/// Returns 0 if the clock can not be read.
+#[cfg(all(target_arch = "wasm32", target_os = "wasi"))]
+pub fn cpu_ticks() -> u64 {
+    const CLOCK_MONOTONIC: u32 = 1;
+    let mut time = 0;
+    let errno = unsafe { clock_time_get(CLOCK_MONOTONIC, 1, &mut time) };
+    if errno == 0 {
+        time
+    } else {
+        0
+    }
+}
+
+/// WebAssembly executes the instructions in order, so the timestamps do not need to be serialized.
+#[cfg(all(
+    feature = "std",
+    target_arch = "wasm32",
+    any(target_os = "unknown", target_os = "wasi")
+))]
+pub(crate) fn serialized_cpu_ticks_begin() -> u64 {
+    cpu_ticks()
+}
+
+#[cfg(all(
+    feature = "std",
+    target_arch = "wasm32",
+    any(target_os = "unknown", target_os = "wasi")
+))]
+pub(crate) fn serialized_cpu_ticks_end() -> u64 {
+    cpu_ticks()
+}
+
+#[cfg(all(
+    feature = "instant-timer",
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+    ))
+))]
+thread_local! {
+    /// The reference point of the fallback ticks of the current thread.
+    static EPOCH: Instant = Instant::now();
+}
+
+/// Returns the nanoseconds since the first call in the current thread, as there is no cycle counter
+/// for this architecture.
+#[cfg(all(
+    feature = "instant-timer",
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+    ))
+))]
+pub fn cpu_ticks() -> u64 {
+    EPOCH.with(|epoch| epoch.elapsed().as_nanos() as u64)
+}
+
+#[cfg(all(
+    feature = "instant-timer",
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+    ))
+))]
+pub(crate) fn serialized_cpu_ticks_begin() -> u64 {
+    cpu_ticks()
+}
+
+#[cfg(all(
+    feature = "instant-timer",
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        all(target_arch = "wasm32", any(target_os = "unknown", target_os = "wasi"))
+    ))
+))]
+pub(crate) fn serialized_cpu_ticks_end() -> u64 {
+    cpu_ticks()
+}